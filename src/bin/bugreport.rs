@@ -0,0 +1,210 @@
+//! Generate a bug report from a manifest file, e.g. for tools that are not written in Rust.
+//!
+//! ```toml
+//! name = "mytool"
+//! version = "1.2.3"
+//! format = "markdown"  # or "plaintext", "json", "html"
+//! output = "report.md" # optional, the report is printed otherwise
+//!
+//! [[collector]]
+//! type = "software_version"
+//!
+//! [[collector]]
+//! type = "command_output"
+//! title = "Python version"
+//! command = "python"
+//! args = ["--version"]
+//! ```
+//!
+//! See [`bugreport::config`] for all collectors.
+
+use std::ffi::OsString;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use serde::Deserialize;
+
+use bugreport::BugReport;
+use bugreport::config::{CollectorConfig, Config};
+use bugreport::format::{Html, Json, Markdown, Plaintext};
+
+const USAGE: &str = "\
+Usage: bugreport [OPTIONS] <MANIFEST>
+
+Generate a bug report from the collectors listed in MANIFEST (a TOML file, or '-' to read
+it from stdin).
+
+Options:
+  -f, --format <FORMAT>  Output format: markdown, plaintext, json or html (overrides the
+                         manifest)
+  -o, --output <FILE>    Write the report to FILE instead of stdout (overrides the manifest)
+  -h, --help             Print this help
+  -V, --version          Print the version of bugreport";
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    name: String,
+    #[serde(default)]
+    version: String,
+    format: Option<OutputFormat>,
+    output: Option<PathBuf>,
+    #[serde(default, rename = "collector")]
+    collectors: Vec<CollectorConfig>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+enum OutputFormat {
+    Markdown,
+    Plaintext,
+    Json,
+    Html,
+}
+
+impl OutputFormat {
+    fn from_name(name: &str) -> Result<Self, String> {
+        match name {
+            "markdown" => Ok(OutputFormat::Markdown),
+            "plaintext" => Ok(OutputFormat::Plaintext),
+            "json" => Ok(OutputFormat::Json),
+            "html" => Ok(OutputFormat::Html),
+            _ => Err(format!(
+                "unknown format '{}', expected markdown, plaintext, json or html",
+                name
+            )),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Options {
+    manifest: Option<PathBuf>,
+    format: Option<OutputFormat>,
+    output: Option<PathBuf>,
+}
+
+enum Command {
+    Run(Options),
+    Help,
+    Version,
+}
+
+fn parse_args(args: impl IntoIterator<Item = OsString>) -> Result<Command, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("missing value for '{}'", name))
+        };
+
+        match arg.to_str() {
+            Some("-h" | "--help") => return Ok(Command::Help),
+            Some("-V" | "--version") => return Ok(Command::Version),
+            Some(name @ ("-f" | "--format")) => {
+                let format = value(name)?;
+                options.format = Some(OutputFormat::from_name(&format.to_string_lossy())?);
+            }
+            Some(name @ ("-o" | "--output")) => options.output = Some(value(name)?.into()),
+            Some(option) if option.starts_with('-') && option != "-" => {
+                return Err(format!("unknown option '{}'", option));
+            }
+            _ if options.manifest.is_some() => {
+                return Err(format!("unexpected argument '{}'", arg.to_string_lossy()));
+            }
+            _ => options.manifest = Some(arg.into()),
+        }
+    }
+
+    Ok(Command::Run(options))
+}
+
+fn read_manifest(path: &Path) -> Result<Manifest, String> {
+    let content = if path.as_os_str() == "-" {
+        let mut content = String::new();
+        io::stdin()
+            .read_to_string(&mut content)
+            .map_err(|e| format!("could not read manifest from stdin: {}", e))?;
+        content
+    } else {
+        std::fs::read_to_string(path).map_err(|e| {
+            format!(
+                "could not read manifest '{}': {}",
+                path.to_string_lossy(),
+                e
+            )
+        })?
+    };
+
+    toml::from_str(&content).map_err(|e| format!("invalid manifest: {}", e))
+}
+
+fn run(options: Options) -> Result<(), String> {
+    let manifest_path = options
+        .manifest
+        .ok_or_else(|| format!("missing MANIFEST argument\n\n{}", USAGE))?;
+    let manifest = read_manifest(&manifest_path)?;
+
+    let mut report =
+        BugReport::from_name_and_version(&manifest.name, &manifest.version).config(Config {
+            collectors: manifest.collectors,
+        });
+
+    let format = options
+        .format
+        .or(manifest.format)
+        .unwrap_or(OutputFormat::Markdown);
+
+    match options.output.or(manifest.output) {
+        Some(path) => std::fs::File::create(&path)
+            .and_then(|file| write_report(&mut report, format, file))
+            .map_err(|e| {
+                format!(
+                    "could not write report to '{}': {}",
+                    path.to_string_lossy(),
+                    e
+                )
+            }),
+        None => write_report(&mut report, format, io::stdout().lock())
+            .map_err(|e| format!("could not print report: {}", e)),
+    }
+}
+
+fn write_report(
+    report: &mut BugReport,
+    format: OutputFormat,
+    writer: impl Write,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Markdown => report.write_to::<Markdown, _>(writer),
+        OutputFormat::Plaintext => report.write_to::<Plaintext, _>(writer),
+        OutputFormat::Json => report.write_to::<Json, _>(writer),
+        OutputFormat::Html => report.write_to::<Html, _>(writer),
+    }
+}
+
+fn main() -> ExitCode {
+    let result = match parse_args(std::env::args_os().skip(1)) {
+        Ok(Command::Run(options)) => run(options),
+        Ok(Command::Help) => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Ok(Command::Version) => {
+            println!("bugreport {}", env!("CARGO_PKG_VERSION"));
+            Ok(())
+        }
+        Err(message) => Err(format!("{}\n\n{}", message, USAGE)),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("bugreport: error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
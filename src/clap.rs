@@ -0,0 +1,194 @@
+//! Add a `--bugreport` option to a command line interface that is built with `clap`.
+//!
+//! ```no_run
+//! use bugreport::{bugreport, collector::*};
+//! use clap::Parser;
+//!
+//! #[derive(Parser)]
+//! struct Cli {
+//!     #[command(flatten)]
+//!     bugreport: bugreport::clap::Args,
+//! }
+//!
+//! let cli = Cli::parse();
+//!
+//! // Exits the program if `--bugreport` was given
+//! cli.bugreport.handle(|| {
+//!     bugreport!()
+//!         .info(SoftwareVersion::default())
+//!         .info(OperatingSystem::default())
+//! });
+//! ```
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::BugReport;
+#[cfg(feature = "format_html")]
+use crate::format::Html;
+#[cfg(feature = "format_json")]
+use crate::format::Json;
+use crate::format::Markdown;
+#[cfg(feature = "format_plaintext")]
+use crate::format::Plaintext;
+
+/// Command line options to print (or write) a bug report.
+///
+/// * `--bugreport[=FORMAT]` prints the report in the given format (Markdown by default)
+/// * `--bugreport-output FILE` writes it to a file instead
+#[derive(Debug, Clone, Default, PartialEq, Eq, ::clap::Args)]
+pub struct Args {
+    /// Print a bug report with information about your system and environment, and exit
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "markdown"
+    )]
+    pub bugreport: Option<ReportFormat>,
+
+    /// Write the bug report to FILE instead of printing it
+    #[arg(long, value_name = "FILE", requires = "bugreport")]
+    pub bugreport_output: Option<PathBuf>,
+}
+
+/// The formats that can be selected with `--bugreport=FORMAT`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ::clap::ValueEnum)]
+pub enum ReportFormat {
+    #[default]
+    Markdown,
+    #[cfg(feature = "format_plaintext")]
+    Plaintext,
+    #[cfg(feature = "format_json")]
+    Json,
+    #[cfg(feature = "format_html")]
+    Html,
+}
+
+impl ReportFormat {
+    fn write_to(self, report: &mut BugReport, writer: impl Write) -> io::Result<()> {
+        match self {
+            ReportFormat::Markdown => report.write_to::<Markdown, _>(writer),
+            #[cfg(feature = "format_plaintext")]
+            ReportFormat::Plaintext => report.write_to::<Plaintext, _>(writer),
+            #[cfg(feature = "format_json")]
+            ReportFormat::Json => report.write_to::<Json, _>(writer),
+            #[cfg(feature = "format_html")]
+            ReportFormat::Html => report.write_to::<Html, _>(writer),
+        }
+    }
+}
+
+impl Args {
+    /// Whether a bug report has been requested.
+    pub fn is_requested(&self) -> bool {
+        self.bugreport.is_some()
+    }
+
+    /// If a bug report has been requested, create it with `builder`, print it (or write it
+    /// to the requested file), and exit the program. Does nothing otherwise.
+    pub fn handle<'a>(&self, builder: impl FnOnce() -> BugReport<'a>) {
+        if !self.is_requested() {
+            return;
+        }
+
+        match self.write(&mut builder(), &mut io::stdout()) {
+            Ok(()) => std::process::exit(0),
+            Err(e) => {
+                eprintln!("Could not write bug report: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    fn write(&self, report: &mut BugReport, stdout: &mut impl Write) -> io::Result<()> {
+        let format = self.bugreport.unwrap_or_default();
+
+        match &self.bugreport_output {
+            Some(path) => format.write_to(report, fs::File::create(path)?),
+            None => format.write_to(report, stdout),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::clap::Parser;
+
+    use super::*;
+    use crate::collector::SoftwareVersion;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        bugreport: Args,
+
+        input: Option<String>,
+    }
+
+    fn parse(args: &[&str]) -> Result<Args, ::clap::Error> {
+        Cli::try_parse_from(std::iter::once("cli").chain(args.iter().copied()))
+            .map(|cli| cli.bugreport)
+    }
+
+    #[test]
+    fn parse_args() {
+        assert_eq!(parse(&[]).unwrap(), Args::default());
+        assert_eq!(
+            parse(&["--bugreport", "input.txt"]).unwrap(),
+            Args {
+                bugreport: Some(ReportFormat::Markdown),
+                bugreport_output: None,
+            }
+        );
+        assert_eq!(
+            parse(&["--bugreport-output", "report.md", "--bugreport=markdown"]).unwrap(),
+            Args {
+                bugreport: Some(ReportFormat::Markdown),
+                bugreport_output: Some("report.md".into()),
+            }
+        );
+
+        assert!(parse(&["--bugreport=unknown"]).is_err());
+        assert!(parse(&["--bugreport-output", "report.md"]).is_err());
+    }
+
+    #[test]
+    fn write() {
+        let mut report =
+            BugReport::from_name_and_version("dummy", "0.1").info(SoftwareVersion::default());
+        let mut stdout = vec![];
+
+        parse(&["--bugreport"])
+            .unwrap()
+            .write(&mut report, &mut stdout)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(stdout).unwrap(),
+            "#### Software version\n\ndummy 0.1\n\n"
+        );
+    }
+
+    #[test]
+    fn write_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.md");
+        let mut report =
+            BugReport::from_name_and_version("dummy", "0.1").info(SoftwareVersion::default());
+        let mut stdout = vec![];
+
+        parse(&["--bugreport", "--bugreport-output", path.to_str().unwrap()])
+            .unwrap()
+            .write(&mut report, &mut stdout)
+            .unwrap();
+
+        assert!(stdout.is_empty());
+        assert_eq!(
+            fs::read_to_string(path).unwrap(),
+            "#### Software version\n\ndummy 0.1\n\n"
+        );
+    }
+}
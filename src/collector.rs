@@ -1,43 +1,170 @@
 //! Contains all builtin information collectors and the [`Collector`] trait to implement your own.
 
 use std::borrow::Cow;
+use std::error::Error;
 use std::ffi::{OsStr, OsString};
-use std::fmt::Write;
+use std::fmt::{self, Write};
 use std::fs;
+use std::io::{self, ErrorKind};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::time::Duration;
 
 use super::CrateInfo;
 use super::Result;
 
-use crate::helper::StringExt;
+#[cfg(feature = "regex")]
+use crate::Regex;
+use crate::helper::{StringExt, glob_match};
 use crate::report::{Code, ReportEntry};
 
+mod command_output;
 mod directory_entries;
+mod directory_tree;
+mod group;
+pub use command_output::CommandOutput;
 pub use directory_entries::DirectoryEntries;
+pub use directory_tree::DirectoryTree;
+pub use group::Group;
 
 /// Error that appeared while collecting bug report information.
+///
+/// Errors are shown as [`ReportEntry::Error`] in place of the section content, see
+/// [`BugReport::failed_sections`](crate::BugReport::failed_sections) for alternatives.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum CollectionError {
+    /// The information is not available, e.g. because a file or command does not exist.
+    /// Contains the message that is shown in the report.
     CouldNotRetrieve(String),
+    /// The information does not apply (e.g. to this platform). The section is omitted.
+    NotApplicable,
+    /// Access to the given resource was denied.
+    PermissionDenied(String),
+    /// Collecting the information took longer than the given duration.
+    Timeout(Duration),
+    /// An unexpected I/O error, e.g. while running a command or reading a file.
+    Io(io::Error),
 }
 
 impl CollectionError {
     pub(crate) fn to_entry(&self) -> ReportEntry {
+        ReportEntry::Error(self.to_string())
+    }
+
+    /// Convert an I/O error that appeared while accessing `what`.
+    pub(crate) fn from_io(error: io::Error, what: &str) -> Self {
+        use CollectionError::*;
+
+        match error.kind() {
+            ErrorKind::NotFound => CouldNotRetrieve(format!("{} not found", what)),
+            ErrorKind::PermissionDenied => PermissionDenied(what.into()),
+            _ => Self::io(error, format!("{} not read", what)),
+        }
+    }
+
+    /// An [`CollectionError::Io`] whose message starts with `context`. The original error
+    /// is kept as the source.
+    pub(crate) fn io(error: io::Error, context: String) -> Self {
+        CollectionError::Io(io::Error::new(
+            error.kind(),
+            IoContext {
+                context,
+                source: error,
+            },
+        ))
+    }
+}
+
+/// An I/O error with a description of what was being done when it appeared.
+#[derive(Debug)]
+struct IoContext {
+    context: String,
+    source: io::Error,
+}
+
+impl fmt::Display for IoContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.context, self.source)
+    }
+}
+
+impl Error for IoContext {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[test]
+fn test_io_error_source() {
+    let error = CollectionError::io(
+        io::Error::from_raw_os_error(21),
+        "Could not read 'dir'".into(),
+    );
+
+    assert!(error.to_string().starts_with("Could not read 'dir': "));
+
+    let mut os_error = None;
+    let mut source = error.source();
+    while let Some(e) = source {
+        if let Some(code) = e
+            .downcast_ref::<io::Error>()
+            .and_then(io::Error::raw_os_error)
+        {
+            os_error = Some(code);
+        }
+        source = e.source();
+    }
+    assert_eq!(os_error, Some(21));
+}
+
+impl fmt::Display for CollectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use CollectionError::*;
 
         match self {
-            CouldNotRetrieve(reason) => ReportEntry::Text(reason.clone()),
+            CouldNotRetrieve(reason) => write!(f, "{}", reason),
+            NotApplicable => write!(f, "Not applicable"),
+            PermissionDenied(what) => write!(f, "Permission denied: {}", what),
+            Timeout(duration) => write!(f, "Timed out after {} s", duration.as_secs_f64()),
+            Io(e) => write!(f, "{}", e),
         }
     }
 }
 
+impl Error for CollectionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CollectionError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CollectionError {
+    fn from(error: io::Error) -> Self {
+        CollectionError::Io(error)
+    }
+}
+
 /// Implement this trait to define customized information collectors.
+///
+/// Collectors that are also [`Send`] can be run in parallel, see
+/// [`BugReport::parallel_info`](crate::BugReport::parallel_info).
 pub trait Collector {
     fn description(&self) -> &str;
     fn collect(&mut self, crate_info: &CrateInfo) -> Result<ReportEntry>;
 }
 
+impl<C: Collector + ?Sized> Collector for Box<C> {
+    fn description(&self) -> &str {
+        (**self).description()
+    }
+
+    fn collect(&mut self, crate_info: &CrateInfo) -> Result<ReportEntry> {
+        (**self).collect(crate_info)
+    }
+}
+
 /// The name of your crate and the current version.
 #[derive(Default)]
 pub struct SoftwareVersion {
@@ -83,30 +210,18 @@ impl Collector for CompileTimeInformation {
 
     fn collect(&mut self, _: &CrateInfo) -> Result<ReportEntry> {
         Ok(ReportEntry::List(vec![
-            ReportEntry::Text(format!("Profile: {}", env!("BUGREPORT_PROFILE"))),
-            ReportEntry::Text(format!("Target triple: {}", env!("BUGREPORT_TARGET"))),
-            ReportEntry::Text(format!(
-                "Family: {}",
-                env!("BUGREPORT_CARGO_CFG_TARGET_FAMILY")
-            )),
-            ReportEntry::Text(format!("OS: {}", env!("BUGREPORT_CARGO_CFG_TARGET_OS"))),
-            ReportEntry::Text(format!(
-                "Architecture: {}",
-                env!("BUGREPORT_CARGO_CFG_TARGET_ARCH")
-            )),
-            ReportEntry::Text(format!(
-                "Pointer width: {}",
-                env!("BUGREPORT_CARGO_CFG_TARGET_POINTER_WIDTH")
-            )),
-            ReportEntry::Text(format!(
-                "Endian: {}",
-                env!("BUGREPORT_CARGO_CFG_TARGET_ENDIAN")
-            )),
-            ReportEntry::Text(format!(
-                "CPU features: {}",
-                env!("BUGREPORT_CARGO_CFG_TARGET_FEATURE")
-            )),
-            ReportEntry::Text(format!("Host: {}", env!("BUGREPORT_HOST"))),
+            ReportEntry::key_value("Profile", env!("BUGREPORT_PROFILE")),
+            ReportEntry::key_value("Target triple", env!("BUGREPORT_TARGET")),
+            ReportEntry::key_value("Family", env!("BUGREPORT_CARGO_CFG_TARGET_FAMILY")),
+            ReportEntry::key_value("OS", env!("BUGREPORT_CARGO_CFG_TARGET_OS")),
+            ReportEntry::key_value("Architecture", env!("BUGREPORT_CARGO_CFG_TARGET_ARCH")),
+            ReportEntry::key_value(
+                "Pointer width",
+                env!("BUGREPORT_CARGO_CFG_TARGET_POINTER_WIDTH"),
+            ),
+            ReportEntry::key_value("Endian", env!("BUGREPORT_CARGO_CFG_TARGET_ENDIAN")),
+            ReportEntry::key_value("CPU features", env!("BUGREPORT_CARGO_CFG_TARGET_FEATURE")),
+            ReportEntry::key_value("Host", env!("BUGREPORT_HOST")),
         ]))
     }
 }
@@ -153,29 +268,218 @@ impl Collector for OperatingSystem {
 
     fn collect(&mut self, _: &CrateInfo) -> Result<ReportEntry> {
         Ok(ReportEntry::List(vec![
-            ReportEntry::Text(format!(
-                "OS: {}",
+            ReportEntry::key_value(
+                "OS",
                 sysinfo::System::long_os_version().unwrap_or_else(|| "Unknown".to_owned()),
-            )),
-            ReportEntry::Text(format!(
-                "Kernel: {}",
+            ),
+            ReportEntry::key_value(
+                "Kernel",
                 sysinfo::System::kernel_version().unwrap_or_else(|| "Unknown".to_owned()),
-            )),
+            ),
         ]))
     }
 }
 
+/// The current date and time (UTC and local), the time zone and the system uptime.
+#[cfg(feature = "collector_datetime")]
+#[derive(Default)]
+pub struct DateTime {}
+
+#[cfg(feature = "collector_datetime")]
+impl Collector for DateTime {
+    fn description(&self) -> &str {
+        "Date and time"
+    }
+
+    fn collect(&mut self, _: &CrateInfo) -> Result<ReportEntry> {
+        let utc = chrono::Utc::now();
+        let local = utc.with_timezone(&chrono::Local);
+
+        Ok(ReportEntry::List(vec![
+            ReportEntry::key_value("UTC", utc.format("%Y-%m-%d %H:%M:%S").to_string()),
+            ReportEntry::key_value("Local time", local.format("%Y-%m-%d %H:%M:%S").to_string()),
+            ReportEntry::key_value(
+                "Time zone",
+                time_zone_name().unwrap_or_else(|| "Unknown".into()),
+            ),
+            ReportEntry::key_value("UTC offset", local.offset().to_string()),
+            ReportEntry::key_value("System uptime", format_uptime(sysinfo::System::uptime())),
+        ]))
+    }
+}
+
+/// The IANA name of the local time zone, from `TZ` or the target of `/etc/localtime`.
+#[cfg(feature = "collector_datetime")]
+fn time_zone_name() -> Option<String> {
+    if let Some(tz) = std::env::var_os("TZ") {
+        let tz = tz.to_string_lossy();
+        let tz = tz.strip_prefix(':').unwrap_or(&tz);
+        if !tz.is_empty() {
+            return Some(tz.into());
+        }
+    }
+
+    fs::read_link("/etc/localtime")
+        .ok()
+        .and_then(|target| time_zone_name_from_path(&target))
+}
+
+#[cfg(feature = "collector_datetime")]
+fn time_zone_name_from_path(path: &Path) -> Option<String> {
+    let path = path.to_string_lossy();
+    let (_, name) = path.split_once("zoneinfo/")?;
+    Some(name.into())
+}
+
+#[cfg(feature = "collector_datetime")]
+fn format_uptime(seconds: u64) -> String {
+    let days = seconds / 86400;
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+
+    match days {
+        0 => time,
+        1 => format!("1 day, {}", time),
+        _ => format!("{} days, {}", days, time),
+    }
+}
+
+#[cfg(all(test, feature = "collector_datetime"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_zone_name_from_localtime_link() {
+        assert_eq!(
+            time_zone_name_from_path(Path::new("/usr/share/zoneinfo/Europe/Berlin")),
+            Some("Europe/Berlin".into())
+        );
+        assert_eq!(
+            time_zone_name_from_path(Path::new("../usr/share/zoneinfo/UTC")),
+            Some("UTC".into())
+        );
+        assert_eq!(time_zone_name_from_path(Path::new("/etc/timezone")), None);
+    }
+
+    #[test]
+    fn uptime() {
+        assert_eq!(format_uptime(59), "00:00:59");
+        assert_eq!(format_uptime(86400 + 3600 + 61), "1 day, 01:01:01");
+        assert_eq!(format_uptime(3 * 86400 + 7200), "3 days, 02:00:00");
+    }
+}
+
+/// Environment variables whose name contains one of these are masked, unless
+/// [`EnvironmentVariables::show_secrets`] is used.
+const SECRET_NAMES: &[&str] = &["TOKEN", "SECRET", "PASSWORD"];
+
+/// Like [`SECRET_NAMES`], but only `_`-separated parts of the name are compared, so that
+/// e.g. `KEYMAP` or `MONKEY` are not masked.
+const SECRET_NAME_PARTS: &[&str] = &["KEY", "KEYS", "APIKEY", "ACCESSKEY", "PRIVATEKEY"];
+
 /// The values of the specified environment variables (if set).
+///
+/// The values of variables whose name contains `TOKEN`, `SECRET` or `PASSWORD`, or `KEY` as
+/// a `_`-separated part (e.g. `API_KEY` or `MYAPP_APIKEY`, but not `KEYMAP`), are masked
+/// (`<set, N chars>`).
 pub struct EnvironmentVariables {
-    list: Vec<OsString>,
+    selection: Selection,
+    show_secrets: bool,
+}
+
+enum Selection {
+    List(Vec<OsString>),
+    Prefix(String),
+    Glob(String),
+    #[cfg(feature = "regex")]
+    Regex(Regex),
 }
 
 impl EnvironmentVariables {
+    /// The given variables, in this order. Values of variables that look like they contain
+    /// secrets are masked, see [`EnvironmentVariables::show_secrets`].
     pub fn list<S: AsRef<OsStr>>(list: &[S]) -> Self {
+        Self::new(Selection::List(
+            list.iter().map(|s| s.as_ref().to_os_string()).collect(),
+        ))
+    }
+
+    /// All variables whose name starts with `prefix`, e.g. `CARGO_`.
+    pub fn prefix(prefix: &str) -> Self {
+        Self::new(Selection::Prefix(prefix.into()))
+    }
+
+    /// All variables whose name matches the glob pattern (`*` and `?` are supported), e.g.
+    /// `MYAPP_*`.
+    pub fn glob(pattern: &str) -> Self {
+        Self::new(Selection::Glob(pattern.into()))
+    }
+
+    /// All variables whose name matches the regular expression, see [`Regex`].
+    #[cfg(feature = "regex")]
+    pub fn regex(regex: Regex) -> Self {
+        Self::new(Selection::Regex(regex))
+    }
+
+    /// Do not mask the values of variables that look like they contain secrets.
+    pub fn show_secrets(mut self) -> Self {
+        self.show_secrets = true;
+        self
+    }
+
+    fn new(selection: Selection) -> Self {
         Self {
-            list: list.iter().map(|s| s.as_ref().to_os_string()).collect(),
+            selection,
+            show_secrets: false,
+        }
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        match &self.selection {
+            Selection::List(list) => list.iter().any(|var| var == name),
+            Selection::Prefix(prefix) => name.starts_with(prefix.as_str()),
+            Selection::Glob(pattern) => glob_match(pattern, name),
+            #[cfg(feature = "regex")]
+            Selection::Regex(regex) => regex.is_match(name),
         }
     }
+
+    fn format_value(&self, name: &str, value: &str) -> String {
+        if !self.show_secrets && is_secret(name) {
+            format!("<set, {} chars>", value.chars().count())
+        } else {
+            shell_escape::escape(Cow::Borrowed(value)).into()
+        }
+    }
+}
+
+fn is_secret(name: &str) -> bool {
+    let name = name.to_uppercase();
+
+    SECRET_NAMES.iter().any(|secret| name.contains(secret))
+        || name
+            .split('_')
+            .any(|part| SECRET_NAME_PARTS.contains(&part))
+}
+
+#[test]
+fn test_is_secret() {
+    assert!(is_secret("GITHUB_TOKEN"));
+    assert!(is_secret("api_key"));
+    assert!(is_secret("KEY_FILE"));
+    assert!(is_secret("AWS_SECRET_ACCESS_KEY"));
+    assert!(is_secret("DB_PASSWORD"));
+    assert!(is_secret("MYAPP_APIKEY"));
+    assert!(is_secret("GITHUBTOKEN"));
+    assert!(is_secret("DB_PASSWORDS"));
+
+    assert!(!is_secret("KEYMAP"));
+    assert!(!is_secret("XKB_DEFAULT_KEYMAP"));
+    assert!(!is_secret("MONKEY_BUSINESS"));
 }
 
 impl Collector for EnvironmentVariables {
@@ -184,19 +488,41 @@ impl Collector for EnvironmentVariables {
     }
 
     fn collect(&mut self, _: &CrateInfo) -> Result<ReportEntry> {
-        let mut result = String::new();
+        let vars: Vec<(String, Option<String>)> = match &self.selection {
+            Selection::List(list) => list
+                .iter()
+                .map(|var| {
+                    let value = std::env::var_os(var).map(|v| v.to_string_lossy().into_owned());
+                    (var.to_string_lossy().into_owned(), value)
+                })
+                .collect(),
+            _ => {
+                let mut vars: Vec<_> = std::env::vars_os()
+                    .map(|(var, value)| {
+                        let value = value.to_string_lossy().into_owned();
+                        (var.to_string_lossy().into_owned(), Some(value))
+                    })
+                    .filter(|(var, _)| self.matches(var))
+                    .collect();
+                // For stable ordering
+                vars.sort();
+                vars
+            }
+        };
 
-        for var in &self.list {
-            let value = std::env::var_os(var).map(|value| value.to_string_lossy().into_owned());
-            let value: Option<String> =
-                value.map(|v| shell_escape::escape(Cow::Borrowed(&v)).into());
+        if vars.is_empty() {
+            return Ok(ReportEntry::Text(
+                "No matching environment variables are set.".into(),
+            ));
+        }
 
-            let _ = writeln!(
-                result,
-                "{}={}",
-                var.to_string_lossy(),
-                value.unwrap_or_else(|| "<not set>".into())
-            );
+        let mut result = String::new();
+        for (var, value) in vars {
+            let value = match value {
+                Some(value) => self.format_value(&var, &value),
+                None => "<not set>".into(),
+            };
+            let _ = writeln!(result, "{}={}", var, value);
         }
         result.pop();
 
@@ -207,121 +533,95 @@ impl Collector for EnvironmentVariables {
     }
 }
 
-/// The stdout and stderr output (+ exit code) of a custom command.
-pub struct CommandOutput<'a> {
-    title: &'a str,
-    cmd: OsString,
-    cmd_args: Vec<OsString>,
+/// The current working directory and whether it is inside a git worktree. Optionally lists
+/// the entries of the directory (see [`DirectoryEntries`]).
+#[derive(Default)]
+pub struct CurrentWorkingDirectory {
+    list_entries: bool,
 }
 
-impl<'a> CommandOutput<'a> {
-    pub fn new<S, T>(title: &'a str, cmd: T, args: &[S]) -> Self
-    where
-        T: AsRef<OsStr>,
-        S: AsRef<OsStr>,
-    {
-        let mut cmd_args: Vec<OsString> = Vec::new();
-        for a in args {
-            cmd_args.push(a.into());
-        }
-
-        CommandOutput {
-            title,
-            cmd: cmd.as_ref().to_owned(),
-            cmd_args,
-        }
+impl CurrentWorkingDirectory {
+    /// Also list the entries of the current working directory.
+    pub fn with_entries(mut self) -> Self {
+        self.list_entries = true;
+        self
     }
 }
 
-impl Collector for CommandOutput<'_> {
+impl Collector for CurrentWorkingDirectory {
     fn description(&self) -> &str {
-        self.title
+        "Current working directory"
     }
 
     fn collect(&mut self, _: &CrateInfo) -> Result<ReportEntry> {
-        let mut result = String::new();
-
-        result += "> ";
-        result += &self.cmd.to_string_lossy();
-        for arg in &self.cmd_args {
-            result += " ";
-            result += &shell_escape::escape(arg.to_string_lossy());
-        }
-
-        result += "\n";
-
-        let output = Command::new(&self.cmd)
-            .args(&self.cmd_args)
-            .output()
-            .map_err(|e| {
-                CollectionError::CouldNotRetrieve(format!(
-                    "Could not run command '{}': {}",
-                    self.cmd.to_string_lossy(),
-                    e
-                ))
-            })?;
-
-        let utf8_decoding_error = |_| {
-            CollectionError::CouldNotRetrieve(format!(
-                "Error while running command '{}': output is not valid UTF-8.",
-                self.cmd.to_string_lossy()
-            ))
-        };
-
-        let stdout = String::from_utf8(output.stdout).map_err(utf8_decoding_error)?;
-        let stderr = String::from_utf8(output.stderr).map_err(utf8_decoding_error)?;
-
-        result += &stdout;
-        result += &stderr;
+        let cwd = std::env::current_dir().map_err(|e| {
+            CollectionError::CouldNotRetrieve(if e.kind() == ErrorKind::NotFound {
+                "The current working directory does not exist (anymore).".into()
+            } else {
+                format!("Could not determine the current working directory: {}.", e)
+            })
+        })?;
 
-        result.trim_end_inplace();
+        let git_worktree = cwd
+            .ancestors()
+            .find(|dir| dir.join(".git").exists())
+            .map_or_else(
+                || "no".into(),
+                |root| format!("yes ({})", root.to_string_lossy()),
+            );
 
-        let mut concat = vec![ReportEntry::Code(Code {
-            language: None,
-            code: result,
-        })];
-
-        if !output.status.success() {
-            concat.push(ReportEntry::Text(format!(
-                "Command failed{}.",
-                output
-                    .status
-                    .code()
-                    .map_or("".into(), |c| format!(" with exit code {}", c))
-            )));
+        let info = ReportEntry::List(vec![
+            ReportEntry::key_value("Path", cwd.to_string_lossy()),
+            ReportEntry::key_value("Inside git worktree", git_worktree),
+        ]);
+
+        if self.list_entries {
+            let entries = directory_entries::list_directory(&cwd).unwrap_or_else(|e| e.to_entry());
+
+            Ok(ReportEntry::Concat(vec![
+                info,
+                ReportEntry::Text("\nEntries:".into()),
+                entries,
+            ]))
+        } else {
+            Ok(info)
         }
-
-        Ok(ReportEntry::Concat(concat))
     }
 }
 
 /// The full content of a text file.
-pub struct FileContent<'a> {
-    title: &'a str,
+pub struct FileContent {
+    title: String,
     path: PathBuf,
 }
 
-impl<'a> FileContent<'a> {
-    pub fn new<P: AsRef<Path>>(title: &'a str, path: P) -> Self {
+impl FileContent {
+    pub fn new<P: AsRef<Path>>(title: &str, path: P) -> Self {
         Self {
-            title,
+            title: title.into(),
             path: path.as_ref().to_path_buf(),
         }
     }
 }
 
-impl Collector for FileContent<'_> {
+impl Collector for FileContent {
     fn description(&self) -> &str {
-        self.title
+        &self.title
     }
 
     fn collect(&mut self, _: &CrateInfo) -> Result<ReportEntry> {
         let mut result = fs::read_to_string(&self.path).map_err(|e| {
-            CollectionError::CouldNotRetrieve(format!(
-                "Could not read contents of '{}': {}.",
-                self.path.to_string_lossy(),
-                e
-            ))
+            let path = self.path.to_string_lossy();
+            match e.kind() {
+                ErrorKind::PermissionDenied => {
+                    CollectionError::PermissionDenied(format!("'{}'", path))
+                }
+                ErrorKind::NotFound => CollectionError::CouldNotRetrieve(format!(
+                    "Could not read contents of '{}': {}.",
+                    path, e
+                )),
+                _ => CollectionError::io(e, format!("Could not read contents of '{}'", path)),
+            }
         })?;
 
         result.trim_end_inplace();
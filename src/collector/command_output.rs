@@ -0,0 +1,427 @@
+use std::ffi::{OsStr, OsString};
+use std::io::{ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::helper::StringExt;
+use crate::report::{Code, ReportEntry};
+use crate::{Collector, CrateInfo, Result};
+
+use super::CollectionError;
+
+/// How long to wait for the remaining output after a timed-out command has been killed.
+const GRACE_PERIOD: Duration = Duration::from_millis(100);
+
+/// The stdout and stderr output (+ exit code) of a custom command.
+///
+/// By default, stdout is followed by stderr in a single code block. Use
+/// [`CommandOutput::separate_streams`] or [`CommandOutput::interleave_streams`] to change
+/// this. Output that is not valid UTF-8 is decoded lossily and marked with a note.
+///
+/// The command does not receive any input (stdin is connected to the null device), unless
+/// [`CommandOutput::stdin`] is used.
+pub struct CommandOutput {
+    title: String,
+    cmd: OsString,
+    cmd_args: Vec<OsString>,
+    timeout: Option<Duration>,
+    max_output_bytes: Option<usize>,
+    stdin: Option<Vec<u8>>,
+    envs: Vec<(OsString, OsString)>,
+    current_dir: Option<PathBuf>,
+    streams: Streams,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Streams {
+    /// stdout followed by stderr
+    Combined,
+    /// stdout and stderr in separate, labelled blocks
+    Separate,
+    /// stdout and stderr in the order in which the output arrived
+    Interleaved,
+}
+
+impl CommandOutput {
+    pub fn new<S, T>(title: &str, cmd: T, args: &[S]) -> Self
+    where
+        T: AsRef<OsStr>,
+        S: AsRef<OsStr>,
+    {
+        let mut cmd_args: Vec<OsString> = Vec::new();
+        for a in args {
+            cmd_args.push(a.into());
+        }
+
+        CommandOutput {
+            title: title.into(),
+            cmd: cmd.as_ref().to_owned(),
+            cmd_args,
+            timeout: None,
+            max_output_bytes: None,
+            stdin: None,
+            envs: vec![],
+            current_dir: None,
+            streams: Streams::Combined,
+        }
+    }
+
+    /// Show stdout and stderr in separate, labelled code blocks.
+    pub fn separate_streams(mut self) -> Self {
+        self.streams = Streams::Separate;
+        self
+    }
+
+    /// Show stdout and stderr in a single code block, in the order in which the output
+    /// arrived. Note that the order is only approximate if both streams are written to
+    /// in quick succession.
+    pub fn interleave_streams(mut self) -> Self {
+        self.streams = Streams::Interleaved;
+        self
+    }
+
+    /// Kill the command if it does not finish within the given time. The output that was
+    /// produced until then is still included in the report. If there was no output, the
+    /// collector fails with [`CollectionError::Timeout`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Only include the first `max_output_bytes` bytes of the (combined) output.
+    pub fn max_output_bytes(mut self, max_output_bytes: usize) -> Self {
+        self.max_output_bytes = Some(max_output_bytes);
+        self
+    }
+
+    /// Pass the given data to the command on stdin, which is closed afterwards.
+    pub fn stdin<B: Into<Vec<u8>>>(mut self, input: B) -> Self {
+        self.stdin = Some(input.into());
+        self
+    }
+
+    /// Set an additional environment variable for the command.
+    pub fn env<K: AsRef<OsStr>, V: AsRef<OsStr>>(mut self, key: K, value: V) -> Self {
+        self.envs
+            .push((key.as_ref().to_owned(), value.as_ref().to_owned()));
+        self
+    }
+
+    /// Run the command in the given working directory.
+    pub fn current_dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.current_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    fn spawn(&self) -> std::io::Result<Child> {
+        let mut command = Command::new(&self.cmd);
+        command
+            .args(&self.cmd_args)
+            .envs(self.envs.iter().map(|(k, v)| (k, v)))
+            .stdin(if self.stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        if let Some(dir) = &self.current_dir {
+            command.current_dir(dir);
+        }
+
+        command.spawn()
+    }
+
+    fn run(&self) -> std::io::Result<Output> {
+        let start = Instant::now();
+        let mut child = self.spawn()?;
+
+        if let (Some(mut stdin), Some(input)) = (child.stdin.take(), self.stdin.clone()) {
+            // Writing from a separate thread avoids a deadlock if the command does not
+            // read all of its input before producing output.
+            thread::spawn(move || {
+                let _ = stdin.write_all(&input);
+            });
+        }
+
+        let captured = Arc::new(Mutex::new(Captured {
+            chunks: vec![],
+            remaining: self.max_output_bytes.unwrap_or(usize::MAX),
+            truncated: false,
+        }));
+
+        let readers = [
+            child
+                .stdout
+                .take()
+                .map(|pipe| spawn_reader(pipe, Stream::Stdout, Arc::clone(&captured))),
+            child
+                .stderr
+                .take()
+                .map(|pipe| spawn_reader(pipe, Stream::Stderr, Arc::clone(&captured))),
+        ];
+
+        let status = match self.timeout {
+            Some(timeout) => wait_with_timeout(&mut child, timeout)?,
+            None => Some(child.wait()?),
+        };
+
+        // Processes spawned by the command might inherit the pipes and keep them open, so
+        // do not wait for the readers beyond the timeout (or the grace period, if the
+        // command has been killed). The output captured until then is reported.
+        let deadline = match (self.timeout, status) {
+            (None, _) => None,
+            (Some(timeout), Some(_)) => Some(start + timeout),
+            (Some(_), None) => Some(Instant::now() + GRACE_PERIOD),
+        };
+
+        match deadline {
+            None => {
+                for reader in readers.into_iter().flatten() {
+                    let _ = reader.join();
+                }
+            }
+            Some(deadline) => {
+                while Instant::now() < deadline
+                    && !readers.iter().flatten().all(|reader| reader.is_finished())
+                {
+                    thread::sleep(Duration::from_millis(5));
+                }
+            }
+        }
+
+        let captured = captured.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(Output {
+            chunks: captured.chunks.clone(),
+            truncated: captured.truncated,
+            status,
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Stream {
+    Stdout,
+    Stderr,
+}
+
+struct Captured {
+    /// The output in the order in which it arrived. Consecutive chunks of the same stream
+    /// are merged.
+    chunks: Vec<(Stream, Vec<u8>)>,
+    remaining: usize,
+    truncated: bool,
+}
+
+impl Captured {
+    fn push(&mut self, stream: Stream, data: &[u8]) {
+        let len = data.len().min(self.remaining);
+        if len < data.len() {
+            self.truncated = true;
+        }
+        self.remaining -= len;
+
+        if len == 0 {
+            return;
+        }
+
+        match self.chunks.last_mut() {
+            Some((last, chunk)) if *last == stream => chunk.extend_from_slice(&data[..len]),
+            _ => self.chunks.push((stream, data[..len].to_vec())),
+        }
+    }
+}
+
+struct Output {
+    chunks: Vec<(Stream, Vec<u8>)>,
+    truncated: bool,
+    /// `None` if the command timed out
+    status: Option<ExitStatus>,
+}
+
+fn spawn_reader<R: Read + Send + 'static>(
+    mut pipe: R,
+    stream: Stream,
+    captured: Arc<Mutex<Captured>>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let mut buffer = [0; 8192];
+        // Keep reading after the limit is reached, so that the command does not block
+        // on a full pipe.
+        while let Ok(n) = pipe.read(&mut buffer) {
+            if n == 0 {
+                break;
+            }
+            captured
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(stream, &buffer[..n]);
+        }
+    })
+}
+
+/// Wait for the child to exit. Kill it and return `None` if it takes longer than `timeout`.
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> std::io::Result<Option<ExitStatus>> {
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+
+        thread::sleep((timeout - elapsed).min(Duration::from_millis(10)));
+    }
+}
+
+impl Output {
+    /// All output of the given stream.
+    fn stream(&self, stream: Stream) -> Vec<u8> {
+        self.chunks
+            .iter()
+            .filter(|(s, _)| *s == stream)
+            .flat_map(|(_, chunk)| chunk.iter().copied())
+            .collect()
+    }
+}
+
+/// Decode (a part of) the output of a command, replacing invalid UTF-8 sequences. A
+/// multi-byte character that was cut off at the end because the output was truncated is
+/// dropped. The returned flag is set if anything had to be replaced.
+fn decode(mut bytes: Vec<u8>, truncated: bool) -> (String, bool) {
+    if let Err(e) = std::str::from_utf8(&bytes) {
+        if truncated && e.error_len().is_none() {
+            bytes.truncate(e.valid_up_to());
+        }
+    }
+
+    match String::from_utf8(bytes) {
+        Ok(s) => (s, false),
+        Err(e) => (String::from_utf8_lossy(e.as_bytes()).into_owned(), true),
+    }
+}
+
+fn code_block(code: String) -> ReportEntry {
+    ReportEntry::Code(Code {
+        language: None,
+        code,
+    })
+}
+
+impl Collector for CommandOutput {
+    fn description(&self) -> &str {
+        &self.title
+    }
+
+    fn collect(&mut self, _: &CrateInfo) -> Result<ReportEntry> {
+        let mut result = String::new();
+
+        result += "> ";
+        result += &self.cmd.to_string_lossy();
+        for arg in &self.cmd_args {
+            result += " ";
+            result += &shell_escape::escape(arg.to_string_lossy());
+        }
+
+        let output = self.run().map_err(|e| {
+            let cmd = self.cmd.to_string_lossy();
+            match e.kind() {
+                ErrorKind::NotFound => CollectionError::CouldNotRetrieve(format!(
+                    "Could not run command '{}': {}",
+                    cmd, e
+                )),
+                ErrorKind::PermissionDenied => {
+                    CollectionError::PermissionDenied(format!("command '{}'", cmd))
+                }
+                _ => CollectionError::io(e, format!("Could not run command '{}'", cmd)),
+            }
+        })?;
+
+        // Without any output, there is nothing to show but the timeout
+        if output.status.is_none() && output.chunks.is_empty() {
+            return Err(CollectionError::Timeout(self.timeout.unwrap_or_default()));
+        }
+
+        let truncation_note = format!(
+            "[… output truncated after {} bytes]",
+            self.max_output_bytes.unwrap_or_default()
+        );
+
+        let mut invalid_utf8 = false;
+        let mut decode = |bytes| {
+            let (decoded, lossy) = decode(bytes, output.truncated);
+            invalid_utf8 |= lossy;
+            decoded
+        };
+
+        let mut concat = vec![];
+
+        if self.streams == Streams::Separate {
+            concat.push(code_block(result));
+
+            for (stream, label) in [(Stream::Stdout, "stdout:"), (Stream::Stderr, "stderr:")] {
+                let mut decoded = decode(output.stream(stream));
+                decoded.trim_end_inplace();
+                if !decoded.is_empty() {
+                    concat.push(ReportEntry::Text(label.into()));
+                    concat.push(code_block(decoded));
+                }
+            }
+
+            if output.truncated {
+                concat.push(ReportEntry::Text(truncation_note));
+            }
+        } else {
+            result += "\n";
+
+            if self.streams == Streams::Interleaved {
+                for (_, chunk) in &output.chunks {
+                    result += &decode(chunk.clone());
+                }
+            } else {
+                result += &decode(output.stream(Stream::Stdout));
+                result += &decode(output.stream(Stream::Stderr));
+            }
+
+            result.trim_end_inplace();
+
+            if output.truncated {
+                result += "\n";
+                result += &truncation_note;
+            }
+
+            concat.push(code_block(result));
+        }
+
+        if invalid_utf8 {
+            concat.push(ReportEntry::Text(
+                "Output is not valid UTF-8, invalid sequences were replaced by '\u{FFFD}'.".into(),
+            ));
+        }
+
+        match output.status {
+            None => concat.push(ReportEntry::Text(format!(
+                "Command timed out after {} s.",
+                self.timeout.unwrap_or_default().as_secs_f64()
+            ))),
+            Some(status) if !status.success() => concat.push(ReportEntry::Text(format!(
+                "Command failed{}.",
+                status
+                    .code()
+                    .map_or("".into(), |c| format!(" with exit code {}", c))
+            ))),
+            Some(_) => {}
+        }
+
+        Ok(ReportEntry::Concat(concat))
+    }
+}
@@ -1,6 +1,5 @@
 use std::fmt::Write;
 use std::fs::{self, DirEntry};
-use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 
 use crate::{Collector, CrateInfo, Result, report::ReportEntry};
@@ -9,7 +8,7 @@ use super::CollectionError;
 
 /// List information about entries in a directory.
 ///
-/// Limitations:
+/// Limitations (see [`DirectoryTree`](super::DirectoryTree) for a more capable alternative):
 /// * Is not recursive
 /// * Does not handle symbolic links
 /// * Only sizes of files are printed and not e.g. time of last modification
@@ -43,35 +42,38 @@ impl Collector for DirectoryEntries {
     }
 
     fn collect(&mut self, _: &CrateInfo) -> Result<ReportEntry> {
-        let path_str = &self.path.to_string_lossy();
+        list_directory(&self.path)
+    }
+}
 
-        let mut entries = fs::read_dir(&self.path)
-            .map_err(|e| read_dir_error_to_report_entry(e, path_str))?
-            .map(|e| match e {
-                Ok(dir_entry) => dir_entry_to_report_entry(dir_entry),
-                Err(e) => format!("Error: {}", e),
-            })
-            .collect::<Vec<_>>();
+pub(super) fn list_directory(path: &Path) -> Result<ReportEntry> {
+    let path_str = &path.to_string_lossy();
 
-        // For stable ordering
-        entries.sort();
+    let mut entries = fs::read_dir(path)
+        .map_err(|e| read_dir_error_to_report_entry(e, path_str))?
+        .map(|e| match e {
+            Ok(dir_entry) => dir_entry_to_report_entry(dir_entry),
+            Err(e) => format!("Error: {}", e),
+        })
+        .collect::<Vec<_>>();
 
-        if entries.is_empty() {
-            Ok(ReportEntry::Text(format!("'{}' is empty", path_str)))
-        } else {
-            Ok(ReportEntry::List(
-                entries.into_iter().map(ReportEntry::Text).collect(),
-            ))
-        }
+    // For stable ordering
+    entries.sort();
+
+    if entries.is_empty() {
+        Ok(ReportEntry::Text(format!("'{}' is empty", path_str)))
+    } else {
+        Ok(ReportEntry::List(
+            entries.into_iter().map(ReportEntry::Text).collect(),
+        ))
     }
 }
 
-fn read_dir_error_to_report_entry(error: std::io::Error, path_str: &str) -> CollectionError {
-    CollectionError::CouldNotRetrieve(if error.kind() == ErrorKind::NotFound {
-        format!("'{}' not found", path_str)
-    } else {
-        format!("'{}' not read: {}", path_str, error)
-    })
+pub(super) fn read_dir_error_to_report_entry(
+    error: std::io::Error,
+    path_str: &str,
+) -> CollectionError {
+    CollectionError::from_io(error, &format!("'{}'", path_str))
 }
 
 fn dir_entry_to_report_entry(dir_entry: DirEntry) -> String {
@@ -0,0 +1,268 @@
+use std::fmt::Write;
+use std::fs::{self, DirEntry, Metadata};
+use std::path::{Path, PathBuf};
+
+use crate::helper::{UtcDateTime, glob_match};
+use crate::{Collector, CrateInfo, Result, report::ReportEntry};
+
+use super::directory_entries::read_dir_error_to_report_entry;
+
+/// Recursively list the entries of a directory as a nested list.
+///
+/// Symbolic links are not followed, but shown with their target (`name -> target`).
+/// Links whose target does not exist are marked as dangling. Entries are sorted by name.
+///
+/// By default, the whole tree is listed, up to a total of 1000 entries. Use the builder
+/// methods to limit the depth, filter entries, or show more details.
+///
+/// # Example
+///
+/// ```md
+/// #### Config dir
+///
+/// - config.toml, 120 bytes
+/// - current -> themes/dark.toml
+/// - themes/
+///   - dark.toml, 80 bytes
+///   - light.toml, 78 bytes
+///
+/// ```
+pub struct DirectoryTree {
+    title: String,
+    path: PathBuf,
+    max_depth: Option<usize>,
+    max_entries: usize,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    modification_times: bool,
+    permissions: bool,
+}
+
+impl DirectoryTree {
+    pub fn new<P: AsRef<Path>>(title: &str, path: P) -> Self {
+        Self {
+            title: title.into(),
+            path: path.as_ref().to_path_buf(),
+            max_depth: None,
+            max_entries: 1000,
+            include: vec![],
+            exclude: vec![],
+            modification_times: false,
+            permissions: false,
+        }
+    }
+
+    /// Only descend `max_depth` levels into the tree. A depth of 1 lists the entries of the
+    /// directory itself, but not the contents of its subdirectories.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Stop listing entries after the given number of entries.
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Only list files (and symbolic links) whose name matches the given glob pattern
+    /// (`*` and `?` are supported). Directories are always listed. Can be given multiple
+    /// times.
+    pub fn include(mut self, pattern: &str) -> Self {
+        self.include.push(pattern.into());
+        self
+    }
+
+    /// Do not list files or directories whose name matches the given glob pattern (e.g.
+    /// `target` or `*.log`). Can be given multiple times.
+    pub fn exclude(mut self, pattern: &str) -> Self {
+        self.exclude.push(pattern.into());
+        self
+    }
+
+    /// Show the time of last modification of every entry.
+    pub fn modification_times(mut self) -> Self {
+        self.modification_times = true;
+        self
+    }
+
+    /// Show the permissions of every entry.
+    pub fn permissions(mut self) -> Self {
+        self.permissions = true;
+        self
+    }
+
+    fn is_excluded(&self, name: &str) -> bool {
+        self.exclude.iter().any(|pattern| glob_match(pattern, name))
+    }
+
+    fn is_included(&self, name: &str) -> bool {
+        self.include.is_empty() || self.include.iter().any(|pattern| glob_match(pattern, name))
+    }
+
+    fn details(&self, text: &mut String, metadata: &Metadata) {
+        if self.modification_times {
+            if let Ok(modified) = metadata.modified() {
+                let _ = write!(
+                    text,
+                    ", modified {}",
+                    UtcDateTime::from_system_time(modified).to_readable()
+                );
+            }
+        }
+
+        if self.permissions {
+            let _ = write!(text, ", {}", permissions(metadata));
+        }
+    }
+}
+
+impl Collector for DirectoryTree {
+    fn description(&self) -> &str {
+        &self.title
+    }
+
+    fn collect(&mut self, _: &CrateInfo) -> Result<ReportEntry> {
+        let path_str = &self.path.to_string_lossy();
+
+        // Fail early (with the same messages as `DirectoryEntries`) if the directory
+        // itself can not be read.
+        fs::read_dir(&self.path).map_err(|e| read_dir_error_to_report_entry(e, path_str))?;
+
+        let mut walker = Walker {
+            tree: self,
+            remaining: self.max_entries,
+            limit_reached: false,
+        };
+        let entries = walker.list(&self.path, 1);
+
+        if entries.is_empty() {
+            Ok(ReportEntry::Text(format!("'{}' is empty", path_str)))
+        } else {
+            Ok(ReportEntry::List(entries))
+        }
+    }
+}
+
+struct Walker<'a> {
+    tree: &'a DirectoryTree,
+    remaining: usize,
+    /// Whether the note that the limit has been reached has already been added
+    limit_reached: bool,
+}
+
+impl Walker<'_> {
+    fn list(&mut self, dir: &Path, depth: usize) -> Vec<ReportEntry> {
+        let mut dir_entries = match fs::read_dir(dir) {
+            Ok(read_dir) => read_dir.collect::<Vec<_>>(),
+            Err(e) => return vec![ReportEntry::Text(format!("Error: {}", e))],
+        };
+
+        // For stable ordering
+        dir_entries.sort_by_key(|e| e.as_ref().ok().map(DirEntry::file_name));
+
+        let mut result = vec![];
+        for dir_entry in dir_entries {
+            let dir_entry = match dir_entry {
+                Ok(dir_entry) => dir_entry,
+                Err(e) => {
+                    result.push(ReportEntry::Text(format!("Error: {}", e)));
+                    continue;
+                }
+            };
+
+            let name = dir_entry.file_name().to_string_lossy().into_owned();
+            let is_dir = dir_entry.file_type().is_ok_and(|t| t.is_dir());
+
+            if self.tree.is_excluded(&name) || (!is_dir && !self.tree.is_included(&name)) {
+                continue;
+            }
+
+            if self.remaining == 0 {
+                if !self.limit_reached {
+                    self.limit_reached = true;
+                    result.push(ReportEntry::Text(format!(
+                        "… (limit of {} entries reached)",
+                        self.tree.max_entries
+                    )));
+                }
+                break;
+            }
+            self.remaining -= 1;
+
+            result.push(self.entry(&dir_entry, name, depth));
+        }
+
+        result
+    }
+
+    fn entry(&mut self, dir_entry: &DirEntry, name: String, depth: usize) -> ReportEntry {
+        let mut text = name;
+
+        // Does not follow symbolic links
+        let metadata = match dir_entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                let _ = write!(text, ", error: {}", e);
+                return ReportEntry::Text(text);
+            }
+        };
+
+        if metadata.is_symlink() {
+            let path = dir_entry.path();
+            match fs::read_link(&path) {
+                Ok(target) => {
+                    let _ = write!(text, " -> {}", target.to_string_lossy());
+                    if fs::metadata(&path).is_err() {
+                        text += " (dangling)";
+                    }
+                }
+                Err(e) => {
+                    let _ = write!(text, " -> ? ({})", e);
+                }
+            }
+            self.tree.details(&mut text, &metadata);
+            ReportEntry::Text(text)
+        } else if metadata.is_dir() {
+            text.push(std::path::MAIN_SEPARATOR);
+            self.tree.details(&mut text, &metadata);
+
+            let children = if self.tree.max_depth.is_none_or(|max| depth < max) {
+                self.list(&dir_entry.path(), depth + 1)
+            } else {
+                vec![]
+            };
+
+            if children.is_empty() {
+                ReportEntry::Text(text)
+            } else {
+                ReportEntry::Concat(vec![ReportEntry::Text(text), ReportEntry::List(children)])
+            }
+        } else {
+            let _ = write!(text, ", {} bytes", metadata.len());
+            self.tree.details(&mut text, &metadata);
+            ReportEntry::Text(text)
+        }
+    }
+}
+
+#[cfg(unix)]
+fn permissions(metadata: &Metadata) -> String {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = metadata.permissions().mode();
+    let mut result = String::with_capacity(9);
+    for (i, c) in "rwxrwxrwx".chars().enumerate() {
+        result.push(if mode & (1 << (8 - i)) != 0 { c } else { '-' });
+    }
+    result
+}
+
+#[cfg(not(unix))]
+fn permissions(metadata: &Metadata) -> String {
+    if metadata.permissions().readonly() {
+        "read-only".into()
+    } else {
+        "read-write".into()
+    }
+}
@@ -0,0 +1,67 @@
+use crate::report::ReportEntry;
+use crate::{Collector, CrateInfo, Result};
+
+use super::CollectionError;
+
+/// Several collectors under a common heading. Every collector becomes a nested section with
+/// its own (sub-)heading. Errors are always shown in place of the nested section content.
+///
+/// # Example
+///
+/// ````md
+/// #### Environment
+///
+/// ##### Operating system
+///
+/// - OS: Linux (Ubuntu 24.04)
+/// - Kernel: 6.8.0-48-generic
+///
+/// ##### Command-line
+///
+/// ```bash
+/// myapp --verbose
+/// ```
+/// ````
+pub struct Group {
+    title: String,
+    collectors: Vec<Box<dyn Collector>>,
+}
+
+impl Group {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.into(),
+            collectors: vec![],
+        }
+    }
+
+    /// Add a [`Collector`] to the group.
+    pub fn info<C: Collector + 'static>(mut self, collector: C) -> Self {
+        self.collectors.push(Box::new(collector));
+        self
+    }
+}
+
+impl Collector for Group {
+    fn description(&self) -> &str {
+        &self.title
+    }
+
+    fn collect(&mut self, crate_info: &CrateInfo) -> Result<ReportEntry> {
+        let mut sections = vec![];
+
+        for collector in &mut self.collectors {
+            let body = match collector.collect(crate_info) {
+                Ok(entry) => entry,
+                Err(CollectionError::NotApplicable) => continue,
+                Err(e) => e.to_entry(),
+            };
+            sections.push(ReportEntry::Section {
+                title: collector.description().into(),
+                body: Box::new(body),
+            });
+        }
+
+        Ok(ReportEntry::Concat(sections))
+    }
+}
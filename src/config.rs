@@ -0,0 +1,298 @@
+//! Describe collectors in a configuration file instead of code.
+//!
+//! This allows to add collectors to a report without releasing a new version of the
+//! application, e.g. by shipping a `bugreport.toml` file along with it:
+//!
+//! ```no_run
+//! use bugreport::{bugreport, collector::*, config::Config, format::Markdown};
+//!
+//! let config = Config::from_file("/usr/share/mytool/bugreport.toml").unwrap_or_default();
+//!
+//! bugreport!()
+//!     .info(SoftwareVersion::default())
+//!     .config(config)
+//!     .print::<Markdown>();
+//! ```
+//!
+//! Every collector is a `[[collector]]` table whose `type` selects the collector, the other
+//! keys correspond to the arguments of its constructor:
+//!
+//! ```toml
+//! [[collector]]
+//! type = "environment_variables"
+//! names = ["SHELL", "EDITOR"]
+//!
+//! [[collector]]
+//! type = "command_output"
+//! title = "Python version"
+//! command = "python"
+//! args = ["--version"]
+//! timeout = 5
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::collector::{self, Collector};
+
+/// A list of collectors, see the [module documentation](self) for the file format.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default, rename = "collector")]
+    pub collectors: Vec<CollectorConfig>,
+}
+
+impl Config {
+    /// Parse a configuration in TOML format, e.g. a string embedded with `include_str!`.
+    pub fn from_toml(toml: &str) -> Result<Self, ConfigError> {
+        toml::from_str(toml).map_err(ConfigError::Parse)
+    }
+
+    /// Read and parse a configuration file in TOML format.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let toml = std::fs::read_to_string(path).map_err(|e| ConfigError::Io(path.into(), e))?;
+        Self::from_toml(&toml)
+    }
+}
+
+/// Error that appeared while loading a [`Config`].
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The file could not be read.
+    Io(PathBuf, io::Error),
+    /// The configuration is not valid.
+    Parse(toml::de::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, e) => {
+                write!(f, "Could not read '{}': {}", path.to_string_lossy(), e)
+            }
+            ConfigError::Parse(e) => write!(f, "Invalid configuration: {}", e),
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConfigError::Io(_, e) => Some(e),
+            ConfigError::Parse(e) => Some(e),
+        }
+    }
+}
+
+/// A single collector, as described in a configuration file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum CollectorConfig {
+    /// See [`SoftwareVersion`](collector::SoftwareVersion). Uses the version of the report,
+    /// unless `version` is given.
+    SoftwareVersion { version: Option<String> },
+    /// See [`OperatingSystem`](collector::OperatingSystem).
+    #[cfg(feature = "collector_operating_system")]
+    OperatingSystem,
+    /// See [`CommandLine`](collector::CommandLine).
+    CommandLine,
+    /// See [`DateTime`](collector::DateTime).
+    #[cfg(feature = "collector_datetime")]
+    DateTime,
+    /// See [`CurrentWorkingDirectory`](collector::CurrentWorkingDirectory).
+    CurrentWorkingDirectory {
+        #[serde(default)]
+        entries: bool,
+    },
+    /// See [`EnvironmentVariables`](collector::EnvironmentVariables).
+    EnvironmentVariables { names: Vec<String> },
+    /// See [`FileContent`](collector::FileContent).
+    FileContent { title: String, path: PathBuf },
+    /// See [`CommandOutput`](collector::CommandOutput). The `timeout` is given in seconds.
+    CommandOutput {
+        title: String,
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+        timeout: Option<u64>,
+        max_output_bytes: Option<usize>,
+    },
+    /// See [`DirectoryEntries`](collector::DirectoryEntries).
+    DirectoryEntries { title: String, path: PathBuf },
+    /// See [`DirectoryTree`](collector::DirectoryTree).
+    DirectoryTree {
+        title: String,
+        path: PathBuf,
+        max_depth: Option<usize>,
+        #[serde(default)]
+        exclude: Vec<String>,
+    },
+}
+
+impl CollectorConfig {
+    /// Create the described collector.
+    pub fn into_collector(self) -> Box<dyn Collector + Send> {
+        use CollectorConfig::*;
+
+        match self {
+            SoftwareVersion { version: None } => Box::new(collector::SoftwareVersion::default()),
+            SoftwareVersion {
+                version: Some(version),
+            } => Box::new(collector::SoftwareVersion::custom(version)),
+            #[cfg(feature = "collector_operating_system")]
+            OperatingSystem => Box::new(collector::OperatingSystem::default()),
+            CommandLine => Box::new(collector::CommandLine::default()),
+            #[cfg(feature = "collector_datetime")]
+            DateTime => Box::new(collector::DateTime::default()),
+            CurrentWorkingDirectory { entries } => {
+                let cwd = collector::CurrentWorkingDirectory::default();
+                Box::new(if entries { cwd.with_entries() } else { cwd })
+            }
+            EnvironmentVariables { names } => {
+                Box::new(collector::EnvironmentVariables::list(&names))
+            }
+            FileContent { title, path } => Box::new(collector::FileContent::new(&title, path)),
+            CommandOutput {
+                title,
+                command,
+                args,
+                timeout,
+                max_output_bytes,
+            } => {
+                let mut output = collector::CommandOutput::new(&title, command, &args);
+                if let Some(timeout) = timeout {
+                    output = output.timeout(Duration::from_secs(timeout));
+                }
+                if let Some(max_output_bytes) = max_output_bytes {
+                    output = output.max_output_bytes(max_output_bytes);
+                }
+                Box::new(output)
+            }
+            DirectoryEntries { title, path } => {
+                Box::new(collector::DirectoryEntries::new(&title, path))
+            }
+            DirectoryTree {
+                title,
+                path,
+                max_depth,
+                exclude,
+            } => {
+                let mut tree = collector::DirectoryTree::new(&title, path);
+                if let Some(max_depth) = max_depth {
+                    tree = tree.max_depth(max_depth);
+                }
+                for pattern in &exclude {
+                    tree = tree.exclude(pattern);
+                }
+                Box::new(tree)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(toml: &str) -> Result<Vec<CollectorConfig>, ConfigError> {
+        Config::from_toml(toml).map(|config| config.collectors)
+    }
+
+    #[test]
+    fn collectors() {
+        let collectors = parse(
+            r#"
+            [[collector]]
+            type = "command_line"
+
+            [[collector]]
+            type = "environment_variables"
+            names = ["SHELL"]
+
+            [[collector]]
+            type = "command_output"
+            title = "Python version"
+            command = "python"
+            args = ["--version"]
+            timeout = 5
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            collectors,
+            vec![
+                CollectorConfig::CommandLine,
+                CollectorConfig::EnvironmentVariables {
+                    names: vec!["SHELL".into()]
+                },
+                CollectorConfig::CommandOutput {
+                    title: "Python version".into(),
+                    command: "python".into(),
+                    args: vec!["--version".into()],
+                    timeout: Some(5),
+                    max_output_bytes: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        assert!(parse("[[collector]]\ntype = \"does_not_exist\"").is_err());
+        assert!(parse("[[collector]]\ntype = \"command_output\"\ntitle = \"a\"\ncommand = \"a\"\ncolour = true").is_err());
+        assert!(parse("[[collector]]\ntype = \"file_content\"\npath = \"a.txt\"").is_err());
+        assert!(parse("[collectors]").is_err());
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(parse("").unwrap(), vec![]);
+    }
+
+    #[test]
+    #[cfg(feature = "format_markdown")]
+    fn extend_report() {
+        use crate::BugReport;
+        use crate::collector::SoftwareVersion;
+        use crate::format::Markdown;
+
+        let config = Config::from_toml(
+            r#"
+            [[collector]]
+            type = "software_version"
+            version = "0.2"
+            "#,
+        )
+        .unwrap();
+
+        let report = BugReport::from_name_and_version("dummy", "0.1")
+            .info(SoftwareVersion::default())
+            .config(config)
+            .format::<Markdown>();
+
+        assert_eq!(
+            report,
+            "#### Software version\n\
+             \n\
+             dummy 0.1\n\
+             \n\
+             #### Software version\n\
+             \n\
+             dummy 0.2\n\n"
+        );
+    }
+
+    #[test]
+    fn file_not_found() {
+        let error = Config::from_file("this-file-does-not-exist.toml").unwrap_err();
+        assert!(matches!(error, ConfigError::Io(_, _)));
+    }
+}
@@ -0,0 +1,223 @@
+use std::io::{BufRead, Write};
+
+#[cfg(feature = "redaction")]
+use crate::redaction::Redaction;
+use crate::report::{Report, ReportEntry};
+
+/// Maximum number of lines shown in the preview of a section.
+const PREVIEW_LINES: usize = 20;
+
+enum Answer {
+    Include,
+    Skip,
+    #[cfg(feature = "redaction")]
+    Redact,
+}
+
+/// Ask the user whether each section of the report may be included. Sections that are
+/// not confirmed (including when `input` ends) are replaced by a short note. Sections that
+/// the user wants to be redacted are rewritten by `redaction` (or the default one).
+pub(crate) fn ask<R: BufRead, W: Write>(
+    report: &mut Report,
+    mut input: R,
+    mut output: W,
+    #[cfg(feature = "redaction")] redaction: Option<&Redaction>,
+) {
+    #[cfg(feature = "redaction")]
+    let default_redaction;
+    #[cfg(feature = "redaction")]
+    let redaction = match redaction {
+        Some(redaction) => redaction,
+        None => {
+            default_redaction = Redaction::default();
+            &default_redaction
+        }
+    };
+
+    for section in &mut report.sections {
+        // Prompts are for the user to fill in, there is nothing to consent to
+        if matches!(section.entry, ReportEntry::Placeholder(_)) {
+            continue;
+        }
+
+        let _ = writeln!(output, "\n{}:\n", section.title);
+        let _ = output.write_all(preview(&section.entry).as_bytes());
+
+        match read_answer(&mut input, &mut output) {
+            Answer::Include => {}
+            Answer::Skip => {
+                section.entry = ReportEntry::Text("Omitted by user.".into());
+            }
+            #[cfg(feature = "redaction")]
+            Answer::Redact => redaction.apply_to_entry(&mut section.entry),
+        }
+    }
+}
+
+fn read_answer<R: BufRead, W: Write>(input: &mut R, output: &mut W) -> Answer {
+    #[cfg(feature = "redaction")]
+    const QUESTION: &str = "Include this information? [y]es, [n]o, [r]edact: ";
+    #[cfg(not(feature = "redaction"))]
+    const QUESTION: &str = "Include this information? [y]es, [n]o: ";
+
+    loop {
+        let _ = write!(output, "\n{}", QUESTION);
+        let _ = output.flush();
+
+        let mut line = String::new();
+        match input.read_line(&mut line) {
+            Ok(0) | Err(_) => return Answer::Skip,
+            Ok(_) => {}
+        }
+
+        match line.trim().to_lowercase().as_str() {
+            "y" | "yes" => return Answer::Include,
+            "n" | "no" => return Answer::Skip,
+            #[cfg(feature = "redaction")]
+            "r" | "redact" => return Answer::Redact,
+            _ => {}
+        }
+    }
+}
+
+fn preview(entry: &ReportEntry) -> String {
+    let mut lines = vec![];
+    preview_lines(entry, &mut lines);
+
+    let mut result = String::new();
+    for line in lines.iter().take(PREVIEW_LINES) {
+        result += "    ";
+        result += line;
+        result += "\n";
+    }
+    if lines.len() > PREVIEW_LINES {
+        result += &format!("    … ({} more lines)\n", lines.len() - PREVIEW_LINES);
+    }
+    result
+}
+
+fn preview_lines(entry: &ReportEntry, lines: &mut Vec<String>) {
+    use ReportEntry::*;
+
+    match entry {
+        Text(content) => lines.extend(content.lines().map(Into::into)),
+        Error(message) => lines.push(format!("[!] {}", message)),
+        Placeholder(hint) => lines.push(format!("[{}]", hint)),
+        Code(c) => lines.extend(c.code.lines().map(Into::into)),
+        List(entries) => {
+            for entry in entries {
+                let mut item = vec![];
+                preview_lines(entry, &mut item);
+                for (i, line) in item.into_iter().enumerate() {
+                    lines.push(format!("{}{}", if i == 0 { "- " } else { "  " }, line));
+                }
+            }
+        }
+        Concat(entries) => {
+            for entry in entries {
+                preview_lines(entry, lines);
+            }
+        }
+        KeyValue { key, value } => lines.push(format!("{}: {}", key, value)),
+        Table { headers, rows } => {
+            lines.push(headers.join(" | "));
+            lines.extend(rows.iter().map(|row| row.join(" | ")));
+        }
+        Section { title, body } => {
+            lines.push(format!("{}:", title));
+            let mut body_lines = vec![];
+            preview_lines(body, &mut body_lines);
+            lines.extend(body_lines.into_iter().map(|line| format!("  {}", line)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::ReportSection;
+
+    fn report() -> Report {
+        let section = |title: &str, text: &str| ReportSection {
+            title: title.into(),
+            entry: ReportEntry::List(vec![ReportEntry::Text(text.into())]),
+        };
+
+        Report {
+            crate_name: "dummy".into(),
+            crate_version: "0.1".into(),
+            git_hash: None,
+            sections: vec![
+                section("First", "one"),
+                section("Second", "two"),
+                section("Third", "three"),
+            ],
+        }
+    }
+
+    #[test]
+    fn scripted_answers() {
+        let mut report = report();
+        let mut output = vec![];
+
+        ask(
+            &mut report,
+            "y\nmaybe\nN\n".as_bytes(),
+            &mut output,
+            #[cfg(feature = "redaction")]
+            None,
+        );
+
+        let entries: Vec<_> = report.sections.into_iter().map(|s| s.entry).collect();
+        assert_eq!(
+            entries,
+            vec![
+                ReportEntry::List(vec![ReportEntry::Text("one".into())]),
+                ReportEntry::Text("Omitted by user.".into()),
+                // Input ended before the third question was answered
+                ReportEntry::Text("Omitted by user.".into()),
+            ]
+        );
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(
+            output.starts_with("\nFirst:\n\n    - one\n\nInclude this information? [y]es, [n]o")
+        );
+        assert_eq!(output.matches("Include this information?").count(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "redaction")]
+    fn configured_redaction() {
+        use crate::Regex;
+
+        let mut report = report();
+        let redaction = Redaction::none().pattern(Regex::new("t[a-z]+").unwrap());
+
+        ask(
+            &mut report,
+            "r\nr\ny\n".as_bytes(),
+            &mut vec![],
+            Some(&redaction),
+        );
+
+        let entries: Vec<_> = report.sections.into_iter().map(|s| s.entry).collect();
+        assert_eq!(
+            entries,
+            vec![
+                ReportEntry::List(vec![ReportEntry::Text("one".into())]),
+                ReportEntry::List(vec![ReportEntry::Text("<redacted>".into())]),
+                ReportEntry::List(vec![ReportEntry::Text("three".into())]),
+            ]
+        );
+    }
+
+    #[test]
+    fn long_preview_is_shortened() {
+        let entry = ReportEntry::Text("line\n".repeat(PREVIEW_LINES + 5));
+
+        let preview = preview(&entry);
+        assert_eq!(preview.lines().count(), PREVIEW_LINES + 1);
+        assert!(preview.ends_with("    … (5 more lines)\n"));
+    }
+}
@@ -0,0 +1,105 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+
+use crate::helper::create_private_temp_file;
+
+const SCISSORS: &str = "------------------------ >8 ------------------------";
+
+const INSTRUCTIONS: &str = "\
+Please review the bug report below before submitting it. Describe what you did,
+what you expected to happen and what happened instead, and remove any
+information that you do not want to share.
+
+Everything above (and including) the following line will be removed.
+";
+
+/// Let the user edit `text` in their editor and return the result.
+pub(crate) fn edit(text: &str) -> io::Result<String> {
+    // The file contains the unredacted report, so other users must not be able to read it
+    let (mut file, path) = create_private_temp_file("bugreport", "txt")?;
+    let written = write!(file, "{}{}\n{}", INSTRUCTIONS, SCISSORS, text).and_then(|_| file.flush());
+    drop(file);
+    if let Err(e) = written {
+        let _ = fs::remove_file(&path);
+        return Err(e);
+    }
+
+    let edited = run_editor(&path).and_then(|_| fs::read_to_string(&path));
+    let _ = fs::remove_file(&path);
+
+    Ok(strip_instructions(&edited?).into())
+}
+
+/// The editor command, similar to what git does: `$VISUAL`, then `$EDITOR`, then a
+/// platform-specific fallback.
+fn editor() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| if cfg!(windows) { "notepad" } else { "vi" }.into())
+}
+
+fn run_editor(path: &Path) -> io::Result<()> {
+    let editor = editor();
+
+    // The editor variable may contain arguments (e.g. `code --wait`)
+    #[cfg(not(windows))]
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$@\"", editor))
+        .arg(&editor)
+        .arg(path)
+        .status();
+
+    #[cfg(windows)]
+    let status = {
+        let mut parts = editor.split_whitespace();
+        Command::new(parts.next().unwrap_or("notepad"))
+            .args(parts)
+            .arg(path)
+            .status()
+    };
+
+    let status = status.map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("Could not run editor '{}': {}", editor, e),
+        )
+    })?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "Editor '{}' failed{}.",
+            editor,
+            status
+                .code()
+                .map_or("".into(), |c| format!(" with exit code {}", c))
+        )))
+    }
+}
+
+fn strip_instructions(text: &str) -> &str {
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        offset += line.len();
+        if line.trim_end() == SCISSORS {
+            return &text[offset..];
+        }
+    }
+
+    // The user removed the scissors line, keep everything.
+    text
+}
+
+#[test]
+fn test_strip_instructions() {
+    let text = format!("{}{}\n#### Title\n\ncontent\n", INSTRUCTIONS, SCISSORS);
+    assert_eq!(strip_instructions(&text), "#### Title\n\ncontent\n");
+
+    assert_eq!(strip_instructions("no scissors\n"), "no scissors\n");
+}
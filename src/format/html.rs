@@ -0,0 +1,230 @@
+use std::io::{self, Write};
+
+use super::{Format, write_to_string};
+use crate::CrateInfo;
+use crate::report::ReportEntry;
+
+const STYLE: &str = "\
+body { font-family: sans-serif; max-width: 60em; margin: 2em auto; padding: 0 1em; color: #222; }
+details { border: 1px solid #ccc; border-radius: 4px; margin: 0.5em 0; padding: 0.5em 1em; }
+summary { cursor: pointer; font-weight: bold; }
+p, div { white-space: pre-wrap; }
+pre { background: #f6f8fa; padding: 0.75em; overflow-x: auto; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 0.25em 0.5em; text-align: left; }
+.key { font-weight: bold; }
+.error { color: #b00020; }
+.placeholder { color: #888; font-style: italic; }
+";
+
+/// Self-contained HTML document (with inline CSS) where every section can be expanded and
+/// collapsed.
+///
+/// # Example
+///
+/// ```html
+/// <details>
+/// <summary>Software version</summary>
+/// <p>dummy 0.1</p>
+/// </details>
+/// ```
+#[derive(Default)]
+pub struct Html {}
+
+impl Format for Html {
+    fn format_header(&mut self, crate_info: &CrateInfo) -> String {
+        let name = escape(&format!(
+            "{} {}",
+            crate_info.pkg_name, crate_info.pkg_version
+        ));
+
+        let mut result = format!(
+            "<!DOCTYPE html>\n\
+             <html>\n\
+             <head>\n\
+             <meta charset=\"utf-8\">\n\
+             <title>Bug report for {}</title>\n\
+             <style>\n{}</style>\n\
+             </head>\n\
+             <body>\n\
+             <h1>Bug report for {}</h1>\n",
+            name, STYLE, name
+        );
+        if let Some(git_hash) = crate_info.git_hash {
+            result += &format!("<p>Git hash: <code>{}</code></p>\n", escape(git_hash));
+        }
+        result
+    }
+
+    fn format_section(&mut self, title: &str) -> String {
+        format!("<details>\n<summary>{}</summary>\n", escape(title))
+    }
+
+    fn format_entry(&mut self, entry: &ReportEntry) -> String {
+        write_to_string(|w| self.write_entry(w, entry))
+    }
+
+    fn write_entry(&mut self, w: &mut dyn Write, entry: &ReportEntry) -> io::Result<()> {
+        use ReportEntry::*;
+
+        match entry {
+            Text(content) => {
+                w.write_all(b"<p>")?;
+                write_escaped(w, content)?;
+                w.write_all(b"</p>\n")
+            }
+            Code(c) => {
+                match &c.language {
+                    Some(language) => {
+                        write!(w, "<pre><code class=\"language-{}\">", escape(language))?
+                    }
+                    None => w.write_all(b"<pre><code>")?,
+                }
+                write_escaped(w, &c.code)?;
+                w.write_all(b"</code></pre>\n")
+            }
+            List(entries) => {
+                w.write_all(b"<ul>\n")?;
+                for entry in entries {
+                    w.write_all(b"<li>")?;
+                    self.write_entry(w, entry)?;
+                    w.write_all(b"</li>\n")?;
+                }
+                w.write_all(b"</ul>\n")
+            }
+            Concat(entries) => {
+                for entry in entries {
+                    self.write_entry(w, entry)?;
+                }
+                Ok(())
+            }
+            KeyValue { key, value } => writeln!(
+                w,
+                "<div><span class=\"key\">{}:</span> {}</div>",
+                escape(key),
+                escape(value)
+            ),
+            Table { headers, rows } => {
+                w.write_all(b"<table>\n<thead>\n")?;
+                table_row(w, "th", headers)?;
+                w.write_all(b"</thead>\n<tbody>\n")?;
+                for row in rows {
+                    table_row(w, "td", row)?;
+                }
+                w.write_all(b"</tbody>\n</table>\n")
+            }
+            Error(message) => writeln!(w, "<p class=\"error\">⚠ {}</p>", escape(message)),
+            Placeholder(hint) => writeln!(w, "<p class=\"placeholder\">{}</p>", escape(hint)),
+            Section { title, body } => {
+                write!(w, "{}", self.format_section(title))?;
+                self.write_entry(w, body)?;
+                w.write_all(b"</details>\n")
+            }
+        }
+    }
+
+    fn format_section_end(&mut self) -> String {
+        "</details>\n".into()
+    }
+
+    fn format_footer(&mut self) -> String {
+        "</body>\n</html>\n".into()
+    }
+}
+
+fn table_row(w: &mut dyn Write, tag: &str, cells: &[String]) -> io::Result<()> {
+    w.write_all(b"<tr>")?;
+    for cell in cells {
+        write!(w, "<{}>{}</{}>", tag, escape(cell), tag)?;
+    }
+    w.write_all(b"</tr>\n")
+}
+
+fn escape(s: &str) -> String {
+    write_to_string(|w| write_escaped(w, s))
+}
+
+fn write_escaped(w: &mut dyn Write, s: &str) -> io::Result<()> {
+    // Write unescaped parts in one go, which matters for long strings like file contents
+    let mut unescaped = 0;
+    for (i, c) in s.char_indices() {
+        let escaped = match c {
+            '&' => "&amp;",
+            '<' => "&lt;",
+            '>' => "&gt;",
+            '"' => "&quot;",
+            '\'' => "&#39;",
+            _ => continue,
+        };
+
+        w.write_all(&s.as_bytes()[unescaped..i])?;
+        w.write_all(escaped.as_bytes())?;
+        unescaped = i + 1;
+    }
+    w.write_all(&s.as_bytes()[unescaped..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BugReport;
+    use crate::collector::SoftwareVersion;
+    use crate::report::Code;
+
+    #[test]
+    fn escaping() {
+        assert_eq!(escape("plain"), "plain");
+        assert_eq!(
+            escape("<a href=\"x\">Tom & 'Jerry'</a>"),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &#39;Jerry&#39;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn entries() {
+        let entry = ReportEntry::List(vec![
+            ReportEntry::key_value("OS", "Linux"),
+            ReportEntry::Code(Code {
+                language: Some("toml".into()),
+                code: "[section]\nkey = \"<value>\"".into(),
+            }),
+        ]);
+
+        assert_eq!(
+            Html::default().format_entry(&entry),
+            "<ul>\n\
+             <li><div><span class=\"key\">OS:</span> Linux</div>\n</li>\n\
+             <li><pre><code class=\"language-toml\">[section]\n\
+             key = &quot;&lt;value&gt;&quot;</code></pre>\n</li>\n\
+             </ul>\n"
+        );
+    }
+
+    #[test]
+    fn multiline_text() {
+        // Line breaks are kept by the style of paragraphs
+        assert!(STYLE.contains("p, div { white-space: pre-wrap; }"));
+        assert_eq!(
+            Html::default().format_entry(&ReportEntry::Text("line1\nline2".into())),
+            "<p>line1\nline2</p>\n"
+        );
+    }
+
+    #[test]
+    fn report() {
+        let report = BugReport::from_name_and_version("dummy", "0.1")
+            .info(SoftwareVersion::default())
+            .format::<Html>();
+
+        assert!(report.starts_with("<!DOCTYPE html>\n<html>\n<head>\n"));
+        assert!(report.ends_with(
+            "<h1>Bug report for dummy 0.1</h1>\n\
+             <details>\n\
+             <summary>Software version</summary>\n\
+             <p>dummy 0.1</p>\n\
+             </details>\n\
+             </body>\n\
+             </html>\n"
+        ));
+    }
+}
@@ -0,0 +1,195 @@
+use std::io::{self, Write};
+
+use super::{Format, write_to_string};
+use crate::CrateInfo;
+use crate::report::ReportEntry;
+
+/// Machine-readable JSON document with the crate information and all sections.
+///
+/// # Example
+///
+/// ```json
+/// {"crate":{"name":"dummy","version":"0.1","git_hash":null},"sections":[
+/// {"title":"Software version","entry":{"type":"text","text":"dummy 0.1"}}
+/// ]}
+/// ```
+#[derive(Default)]
+pub struct Json {
+    past_first_section: bool,
+}
+
+impl Format for Json {
+    fn format_header(&mut self, crate_info: &CrateInfo) -> String {
+        format!(
+            "{{\"crate\":{{\"name\":{},\"version\":{},\"git_hash\":{}}},\"sections\":[\n",
+            string(crate_info.pkg_name),
+            string(crate_info.pkg_version),
+            optional_string(crate_info.git_hash),
+        )
+    }
+
+    fn format_section(&mut self, title: &str) -> String {
+        let separator = if self.past_first_section { ",\n" } else { "" };
+        self.past_first_section = true;
+
+        format!("{}{{\"title\":{},\"entry\":", separator, string(title))
+    }
+
+    fn format_entry(&mut self, entry: &ReportEntry) -> String {
+        write_to_string(|w| self.write_entry(w, entry))
+    }
+
+    fn write_entry(&mut self, w: &mut dyn Write, entry: &ReportEntry) -> io::Result<()> {
+        use ReportEntry::*;
+
+        match entry {
+            Text(content) => {
+                w.write_all(b"{\"type\":\"text\",\"text\":")?;
+                write_string(w, content)?;
+            }
+            Code(c) => {
+                write!(
+                    w,
+                    "{{\"type\":\"code\",\"language\":{},\"code\":",
+                    optional_string(c.language.as_deref())
+                )?;
+                write_string(w, &c.code)?;
+            }
+            List(entries) => {
+                w.write_all(b"{\"type\":\"list\",\"entries\":")?;
+                self.write_entries(w, entries)?;
+            }
+            Concat(entries) => {
+                w.write_all(b"{\"type\":\"concat\",\"entries\":")?;
+                self.write_entries(w, entries)?;
+            }
+            KeyValue { key, value } => {
+                write!(
+                    w,
+                    "{{\"type\":\"key_value\",\"key\":{},\"value\":",
+                    string(key)
+                )?;
+                write_string(w, value)?;
+            }
+            Table { headers, rows } => write!(
+                w,
+                "{{\"type\":\"table\",\"headers\":{},\"rows\":[{}]",
+                strings(headers),
+                rows.iter()
+                    .map(|row| strings(row))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            )?,
+            Error(message) => {
+                w.write_all(b"{\"type\":\"error\",\"message\":")?;
+                write_string(w, message)?;
+            }
+            Placeholder(hint) => {
+                w.write_all(b"{\"type\":\"placeholder\",\"hint\":")?;
+                write_string(w, hint)?;
+            }
+            Section { title, body } => {
+                write!(
+                    w,
+                    "{{\"type\":\"section\",\"title\":{},\"body\":",
+                    string(title)
+                )?;
+                self.write_entry(w, body)?;
+            }
+        }
+
+        w.write_all(b"}")
+    }
+
+    fn format_section_end(&mut self) -> String {
+        "}".into()
+    }
+
+    fn format_footer(&mut self) -> String {
+        "\n]}".into()
+    }
+}
+
+impl Json {
+    fn write_entries(&mut self, w: &mut dyn Write, entries: &[ReportEntry]) -> io::Result<()> {
+        w.write_all(b"[")?;
+        for (i, entry) in entries.iter().enumerate() {
+            if i > 0 {
+                w.write_all(b",")?;
+            }
+            self.write_entry(w, entry)?;
+        }
+        w.write_all(b"]")
+    }
+}
+
+fn strings(strings: &[String]) -> String {
+    let strings: Vec<_> = strings.iter().map(|s| string(s)).collect();
+    format!("[{}]", strings.join(","))
+}
+
+fn optional_string(s: Option<&str>) -> String {
+    s.map_or_else(|| "null".into(), string)
+}
+
+fn string(s: &str) -> String {
+    write_to_string(|w| write_string(w, s))
+}
+
+fn write_string(w: &mut dyn Write, s: &str) -> io::Result<()> {
+    w.write_all(b"\"")?;
+
+    // Write unescaped parts in one go, which matters for long strings like file contents
+    let mut unescaped = 0;
+    for (i, c) in s.char_indices() {
+        let escaped = match c {
+            '"' => Some("\\\""),
+            '\\' => Some("\\\\"),
+            '\n' => Some("\\n"),
+            '\r' => Some("\\r"),
+            '\t' => Some("\\t"),
+            c if c.is_control() => None,
+            _ => continue,
+        };
+
+        w.write_all(&s.as_bytes()[unescaped..i])?;
+        match escaped {
+            Some(escaped) => w.write_all(escaped.as_bytes())?,
+            None => write!(w, "\\u{:04x}", c as u32)?,
+        }
+        unescaped = i + c.len_utf8();
+    }
+    w.write_all(&s.as_bytes()[unescaped..])?;
+
+    w.write_all(b"\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BugReport;
+    use crate::collector::SoftwareVersion;
+
+    #[test]
+    fn string_escaping() {
+        assert_eq!(string("plain"), r#""plain""#);
+        assert_eq!(string("a \"b\" \\ c"), r#""a \"b\" \\ c""#);
+        assert_eq!(string("line\nbreak\t\u{1}"), r#""line\nbreak\t\u0001""#);
+    }
+
+    #[test]
+    fn report() {
+        let report = BugReport::from_name_and_version("dummy", "0.1")
+            .info(SoftwareVersion::default())
+            .info(SoftwareVersion::custom("0.2"))
+            .format::<Json>();
+
+        assert_eq!(
+            report,
+            "{\"crate\":{\"name\":\"dummy\",\"version\":\"0.1\",\"git_hash\":null},\"sections\":[\n\
+             {\"title\":\"Software version\",\"entry\":{\"type\":\"text\",\"text\":\"dummy 0.1\"}},\n\
+             {\"title\":\"Software version\",\"entry\":{\"type\":\"text\",\"text\":\"dummy 0.2\"}}\n\
+             ]}"
+        );
+    }
+}
@@ -1,39 +1,264 @@
-use super::Format;
+use std::io::{self, Write};
+
+use super::{Format, ListItemWriter, write_to_string};
+use crate::CrateInfo;
 use crate::report::ReportEntry;
 
+/// The heading level of top-level sections.
+const SECTION_LEVEL: usize = 4;
+
+/// Sections with more lines are folded by [`GithubMarkdown`].
+const GITHUB_FOLD_THRESHOLD: usize = 25;
+
+/// Markdown document where every section is a level-4 heading.
+///
+/// Use [`BugReport::format_with`](crate::BugReport::format_with) to apply the options,
+/// or [`GithubMarkdown`] for a preset that is suited for GitHub issues.
 #[derive(Default)]
-pub struct Markdown {}
+pub struct Markdown {
+    /// Nesting depth of [`ReportEntry::Section`]s
+    depth: usize,
+    /// Fold sections with more lines than this
+    fold_threshold: Option<usize>,
+    escape_text: bool,
+    /// Title of the current section, if the section may be folded
+    pending_title: Option<String>,
+}
+
+impl Markdown {
+    /// Wrap sections with more than `max_lines` lines in a collapsed
+    /// `<details><summary>Title</summary>…</details>` block (supported by GitHub and GitLab).
+    pub fn fold_sections(mut self, max_lines: usize) -> Self {
+        self.fold_threshold = Some(max_lines);
+        self
+    }
+
+    /// Escape Markdown syntax in text (including titles, keys, values and error messages),
+    /// so that e.g. `*` or `<tag>` are shown literally.
+    pub fn escape_text(mut self) -> Self {
+        self.escape_text = true;
+        self
+    }
+
+    fn text(&self, text: &str) -> String {
+        if self.escape_text {
+            escape(text)
+        } else {
+            text.into()
+        }
+    }
+}
 
 impl Format for Markdown {
     fn format_section(&mut self, title: &str) -> String {
-        format!("#### {}\n\n", title)
+        if self.fold_threshold.is_some() {
+            // The heading is written together with the entry, once its size is known
+            self.pending_title = Some(title.into());
+            return String::new();
+        }
+
+        format!("{} {}\n\n", "#".repeat(SECTION_LEVEL), self.text(title))
     }
 
     fn format_entry(&mut self, entry: &ReportEntry) -> String {
+        write_to_string(|w| self.write_entry(w, entry))
+    }
+
+    fn write_entry(&mut self, w: &mut dyn Write, entry: &ReportEntry) -> io::Result<()> {
         use ReportEntry::*;
 
+        if let Some(title) = self.pending_title.take() {
+            return self.write_foldable_section(w, &title, entry);
+        }
+
         match entry {
-            Text(content) => format!("{}\n", content),
-            Code(c) => format!(
-                "```{}\n{}\n```\n",
-                c.language.as_deref().unwrap_or(""),
-                c.code
-            ),
+            Text(content) => writeln!(w, "{}", self.text(content)),
+            Code(c) => {
+                let fence = fence(&c.code);
+                writeln!(
+                    w,
+                    "{}{}\n{}\n{}",
+                    fence,
+                    c.language.as_deref().unwrap_or(""),
+                    c.code,
+                    fence
+                )
+            }
             List(entries) => {
-                let mut result = String::new();
                 for entry in entries {
-                    result += "- ";
-                    result += &self.format_entry(entry);
+                    w.write_all(b"- ")?;
+                    self.write_entry(&mut ListItemWriter::new(w), entry)?;
                 }
-                result
+                Ok(())
             }
             Concat(entries) => {
-                let mut result = String::new();
-                for entry in entries {
-                    result += &self.format_entry(entry);
+                for (i, entry) in entries.iter().enumerate() {
+                    // Separate nested sections from what precedes them
+                    if i > 0 && matches!(entry, Section { .. }) {
+                        writeln!(w)?;
+                    }
+                    self.write_entry(w, entry)?;
                 }
+                Ok(())
+            }
+            KeyValue { key, value } => writeln!(w, "{}: {}", self.text(key), self.text(value)),
+            Table { headers, rows } => {
+                // A table can not directly follow a paragraph or a list
+                writeln!(w)?;
+                self.table_row(w, headers)?;
+                writeln!(w, "|{}", " --- |".repeat(headers.len()))?;
+                for row in rows {
+                    self.table_row(w, row)?;
+                }
+                Ok(())
+            }
+            Error(message) => writeln!(w, "> ⚠ {}", self.text(message)),
+            Placeholder(hint) => writeln!(w, "<!-- {} -->", comment_text(hint)),
+            Section { title, body } => {
+                // Markdown does not have headings beyond level 6
+                let level = (SECTION_LEVEL + self.depth + 1).min(6);
+                writeln!(w, "{} {}\n", "#".repeat(level), self.text(title))?;
+
+                self.depth += 1;
+                let result = self.write_entry(w, body);
+                self.depth -= 1;
                 result
             }
         }
     }
 }
+
+impl Markdown {
+    fn table_row(&self, w: &mut dyn Write, cells: &[String]) -> io::Result<()> {
+        w.write_all(b"|")?;
+        for cell in cells {
+            let cell = cell.replace('\n', " ");
+            // Escaped text already has `|` escaped
+            let cell = if self.escape_text {
+                escape(&cell)
+            } else {
+                cell.replace('|', "\\|")
+            };
+            write!(w, " {} |", cell)?;
+        }
+        writeln!(w)
+    }
+
+    fn write_foldable_section(
+        &mut self,
+        w: &mut dyn Write,
+        title: &str,
+        entry: &ReportEntry,
+    ) -> io::Result<()> {
+        let body = self.format_entry(entry);
+
+        if self
+            .fold_threshold
+            .is_some_and(|max_lines| body.lines().count() > max_lines)
+        {
+            // The summary is HTML, where Markdown escaping does not apply
+            write!(
+                w,
+                "<details>\n<summary>{}</summary>\n\n{}\n</details>\n",
+                escape_html(title),
+                body
+            )
+        } else {
+            write!(
+                w,
+                "{} {}\n\n{}",
+                "#".repeat(SECTION_LEVEL),
+                self.text(title),
+                body
+            )
+        }
+    }
+}
+
+/// A code fence that is longer than any run of backticks in `code`.
+fn fence(code: &str) -> String {
+    let longest_run = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+
+    "`".repeat(longest_run.max(2) + 1)
+}
+
+/// Escape characters that have a meaning in Markdown (or HTML) with a backslash.
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut line_start = true;
+    for c in text.chars() {
+        let special = matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~' | '!'
+        ) || (line_start && matches!(c, '-' | '+' | '='));
+
+        if special {
+            result.push('\\');
+        }
+        result.push(c);
+        line_start = c == '\n';
+    }
+    result
+}
+
+/// Escape characters that have a meaning in HTML.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Text for an HTML comment, which must not contain `--` (it would end the comment at
+/// `-->`).
+fn comment_text(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut previous = None;
+    for c in text.chars() {
+        if c == '-' && previous == Some('-') {
+            result.push(' ');
+        }
+        result.push(c);
+        previous = Some(c);
+    }
+    result
+}
+
+/// [`Markdown`] for GitHub issues: sections with more than 25 lines are folded, and Markdown
+/// syntax in text is escaped.
+pub struct GithubMarkdown(Markdown);
+
+impl Default for GithubMarkdown {
+    fn default() -> Self {
+        GithubMarkdown(
+            Markdown::default()
+                .fold_sections(GITHUB_FOLD_THRESHOLD)
+                .escape_text(),
+        )
+    }
+}
+
+impl Format for GithubMarkdown {
+    fn format_header(&mut self, crate_info: &CrateInfo) -> String {
+        self.0.format_header(crate_info)
+    }
+
+    fn format_section(&mut self, title: &str) -> String {
+        self.0.format_section(title)
+    }
+
+    fn format_entry(&mut self, entry: &ReportEntry) -> String {
+        self.0.format_entry(entry)
+    }
+
+    fn format_section_end(&mut self) -> String {
+        self.0.format_section_end()
+    }
+
+    fn format_footer(&mut self) -> String {
+        self.0.format_footer()
+    }
+
+    fn write_entry(&mut self, w: &mut dyn Write, entry: &ReportEntry) -> io::Result<()> {
+        self.0.write_entry(w, entry)
+    }
+}
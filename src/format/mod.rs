@@ -1,18 +1,135 @@
 //! Different formats for printing the report.
 
+use std::io::{self, Write};
+
+use crate::CrateInfo;
 use crate::report::ReportEntry;
 
+#[cfg(feature = "format_html")]
+mod html;
+#[cfg(feature = "format_json")]
+mod json;
 #[cfg(feature = "format_markdown")]
 mod markdown;
 #[cfg(feature = "format_plaintext")]
 mod plaintext;
 
+#[cfg(feature = "format_html")]
+pub use html::Html;
+#[cfg(feature = "format_json")]
+pub use json::Json;
 #[cfg(feature = "format_markdown")]
-pub use markdown::Markdown;
+pub use markdown::{GithubMarkdown, Markdown};
 #[cfg(feature = "format_plaintext")]
 pub use plaintext::Plaintext;
 
+/// An output format for the report.
+///
+/// The report is assembled from the `format_*` methods. To write large reports without
+/// building them in memory first (see [`BugReport::write_to`](crate::BugReport::write_to)),
+/// a format can additionally override the corresponding `write_*` methods, in particular
+/// [`Format::write_entry`].
 pub trait Format: Default {
+    /// Called once before the first section, e.g. to open a document.
+    fn format_header(&mut self, _crate_info: &CrateInfo) -> String {
+        String::new()
+    }
+
     fn format_section(&mut self, title: &str) -> String;
     fn format_entry(&mut self, entry: &ReportEntry) -> String;
+
+    /// Called after the entry of each section.
+    fn format_section_end(&mut self) -> String {
+        "\n".into()
+    }
+
+    /// Called once after the last section, e.g. to close a document.
+    fn format_footer(&mut self) -> String {
+        String::new()
+    }
+
+    /// Writer-based counterpart of [`Format::format_header`].
+    fn write_header(&mut self, writer: &mut dyn Write, crate_info: &CrateInfo) -> io::Result<()> {
+        writer.write_all(self.format_header(crate_info).as_bytes())
+    }
+
+    /// Writer-based counterpart of [`Format::format_section`].
+    fn write_section(&mut self, writer: &mut dyn Write, title: &str) -> io::Result<()> {
+        writer.write_all(self.format_section(title).as_bytes())
+    }
+
+    /// Writer-based counterpart of [`Format::format_entry`].
+    fn write_entry(&mut self, writer: &mut dyn Write, entry: &ReportEntry) -> io::Result<()> {
+        writer.write_all(self.format_entry(entry).as_bytes())
+    }
+
+    /// Writer-based counterpart of [`Format::format_section_end`].
+    fn write_section_end(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        writer.write_all(self.format_section_end().as_bytes())
+    }
+
+    /// Writer-based counterpart of [`Format::format_footer`].
+    fn write_footer(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        writer.write_all(self.format_footer().as_bytes())
+    }
+}
+
+/// Collect the output of a writer-based formatting function in a `String`.
+pub(crate) fn write_to_string(write: impl FnOnce(&mut dyn Write) -> io::Result<()>) -> String {
+    let mut buffer = Vec::new();
+    write(&mut buffer).expect("writing to a Vec does not fail");
+    String::from_utf8(buffer).expect("formats only write valid UTF-8")
+}
+
+/// Indents all but the first line written to it, so that multi-line entries (like nested
+/// lists) stay part of a list item. Empty lines are not indented.
+#[cfg(any(feature = "format_markdown", feature = "format_plaintext"))]
+struct ListItemWriter<'a> {
+    inner: &'a mut dyn Write,
+    at_line_start: bool,
+}
+
+#[cfg(any(feature = "format_markdown", feature = "format_plaintext"))]
+impl<'a> ListItemWriter<'a> {
+    fn new(inner: &'a mut dyn Write) -> Self {
+        Self {
+            inner,
+            at_line_start: false,
+        }
+    }
+}
+
+#[cfg(any(feature = "format_markdown", feature = "format_plaintext"))]
+impl Write for ListItemWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for line in buf.split_inclusive(|&b| b == b'\n') {
+            if self.at_line_start && line != b"\n" {
+                self.inner.write_all(b"  ")?;
+            }
+            self.inner.write_all(line)?;
+            self.at_line_start = line.ends_with(b"\n");
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+#[cfg(any(feature = "format_markdown", feature = "format_plaintext"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_item_writer() {
+        let item = write_to_string(|w| {
+            let mut item = ListItemWriter::new(w);
+            item.write_all(b"first\nsec")?;
+            item.write_all(b"ond\n\nthird\n")
+        });
+
+        assert_eq!(item, "first\n  second\n\n  third\n");
+    }
 }
@@ -1,35 +1,119 @@
-use super::Format;
+use std::io::{self, Write};
+
+use super::{Format, ListItemWriter, write_to_string};
 use crate::report::ReportEntry;
 
+/// The width of the ruler of top-level sections.
+const RULER_WIDTH: usize = 48;
+
 #[derive(Default)]
-pub struct Plaintext {}
+pub struct Plaintext {
+    /// Nesting depth of [`ReportEntry::Section`]s
+    depth: usize,
+}
 
 impl Format for Plaintext {
     fn format_section(&mut self, title: &str) -> String {
-        format!("{:-^1$}\n", title, 48)
+        format!("{:-^1$}\n", title, RULER_WIDTH)
     }
 
     fn format_entry(&mut self, entry: &ReportEntry) -> String {
+        write_to_string(|w| self.write_entry(w, entry))
+    }
+
+    fn write_entry(&mut self, w: &mut dyn Write, entry: &ReportEntry) -> io::Result<()> {
         use ReportEntry::*;
 
         match entry {
-            Text(content) => format!("{}\n", content),
-            Code(c) => format!("{}\n", c.code),
+            Text(content) => writeln!(w, "{}", content),
+            Code(c) => writeln!(w, "{}", c.code),
             List(entries) => {
-                let mut result = String::new();
+                // Align the values of all key/value pairs in the list
+                let key_width = entries
+                    .iter()
+                    .filter_map(|entry| match entry {
+                        KeyValue { key, .. } => Some(key.chars().count()),
+                        _ => None,
+                    })
+                    .max()
+                    .unwrap_or(0);
+
                 for entry in entries {
-                    result += "- ";
-                    result += &self.format_entry(entry);
+                    w.write_all(b"- ")?;
+                    match entry {
+                        KeyValue { key, value } => key_value(w, key, value, key_width)?,
+                        entry => self.write_entry(&mut ListItemWriter::new(w), entry)?,
+                    }
                 }
-                result
+                Ok(())
             }
             Concat(entries) => {
-                let mut result = String::new();
-                for entry in entries {
-                    result += &self.format_entry(entry);
+                for (i, entry) in entries.iter().enumerate() {
+                    // Separate nested sections from what precedes them
+                    if i > 0 && matches!(entry, Section { .. }) {
+                        writeln!(w)?;
+                    }
+                    self.write_entry(w, entry)?;
+                }
+                Ok(())
+            }
+            KeyValue { key, value } => key_value(w, key, value, 0),
+            Table { headers, rows } => {
+                let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+                for row in rows {
+                    for (i, cell) in row.iter().enumerate() {
+                        let width = cell.chars().count();
+                        match widths.get_mut(i) {
+                            Some(w) => *w = (*w).max(width),
+                            None => widths.push(width),
+                        }
+                    }
                 }
+
+                let separator: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+
+                table_row(w, headers, &widths)?;
+                table_row(w, &separator, &widths)?;
+                for row in rows {
+                    table_row(w, row, &widths)?;
+                }
+                Ok(())
+            }
+            Error(message) => writeln!(w, "[!] {}", message),
+            Placeholder(hint) => writeln!(w, "[{}]", hint),
+            Section { title, body } => {
+                self.depth += 1;
+                let indent = 2 * self.depth;
+                let result = writeln!(
+                    w,
+                    "{:indent$}{:-^width$}",
+                    "",
+                    title,
+                    indent = indent,
+                    width = RULER_WIDTH.saturating_sub(2 * indent)
+                )
+                .and_then(|_| self.write_entry(w, body));
+                self.depth -= 1;
                 result
             }
         }
     }
 }
+
+fn key_value(w: &mut dyn Write, key: &str, value: &str, key_width: usize) -> io::Result<()> {
+    writeln!(
+        w,
+        "{:<width$} {}",
+        format!("{}:", key),
+        value,
+        width = key_width + 1
+    )
+}
+
+fn table_row(w: &mut dyn Write, cells: &[String], widths: &[usize]) -> io::Result<()> {
+    let mut result = String::new();
+    for (cell, width) in cells.iter().zip(widths) {
+        result += &format!("{:<width$}  ", cell.replace('\n', " "), width = width);
+    }
+    writeln!(w, "{}", result.trim_end())
+}
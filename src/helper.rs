@@ -1,3 +1,10 @@
+use std::collections::hash_map::RandomState;
+use std::fs::{File, OpenOptions};
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
 pub(crate) trait StringExt {
     fn trim_end_inplace(&mut self);
 }
@@ -15,3 +22,181 @@ fn test_trim_end_inplace() {
 
     assert_eq!(s, "test string");
 }
+
+/// Match `text` against a glob `pattern`, in which `*` matches any sequence of characters
+/// and `?` matches a single character.
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    // Position after the last `*` in the pattern and the text position it was matched at
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                p += 1;
+                backtrack = Some((p, t));
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_t)) => {
+                    p = star_p;
+                    t = star_t + 1;
+                    backtrack = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[test]
+fn test_glob_match() {
+    assert!(glob_match("*.toml", "Cargo.toml"));
+    assert!(!glob_match("*.toml", "Cargo.lock"));
+    assert!(glob_match("CARGO_*", "CARGO_HOME"));
+    assert!(glob_match("CARGO_*", "CARGO_"));
+    assert!(!glob_match("CARGO_*", "MY_CARGO_HOME"));
+    assert!(glob_match("a?c*d", "abcxxd"));
+    assert!(glob_match("*a*b*", "xxaxxbxx"));
+    assert!(!glob_match("a?c", "ac"));
+    assert!(glob_match("exact", "exact"));
+    assert!(glob_match("*", ""));
+}
+
+/// Create a new file `<stem>-<random>.<extension>` in the temporary directory that only the
+/// current user can read and write. Never opens an existing file (or follows a symlink that
+/// somebody else placed there), but tries another name instead.
+pub(crate) fn create_private_temp_file(stem: &str, extension: &str) -> io::Result<(File, PathBuf)> {
+    let dir = std::env::temp_dir();
+
+    let mut attempts = 0;
+    loop {
+        let suffix = RandomState::new().build_hasher().finish();
+        let path = dir.join(format!("{}-{:016x}.{}", stem, suffix, extension));
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        match options.open(&path) {
+            Ok(file) => return Ok((file, path)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists && attempts < 100 => {
+                attempts += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[test]
+fn test_create_private_temp_file() {
+    let (_, first) = create_private_temp_file("bugreport-test", "txt").unwrap();
+    let (_, second) = create_private_temp_file("bugreport-test", "txt").unwrap();
+    assert_ne!(first, second);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&first).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    std::fs::remove_file(first).unwrap();
+    std::fs::remove_file(second).unwrap();
+}
+
+/// A point in time in UTC, broken down into its calendar components.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct UtcDateTime {
+    pub(crate) year: i64,
+    pub(crate) month: u32,
+    pub(crate) day: u32,
+    pub(crate) hour: u32,
+    pub(crate) minute: u32,
+    pub(crate) second: u32,
+}
+
+impl UtcDateTime {
+    pub(crate) fn from_unix_timestamp(timestamp: i64) -> Self {
+        let days = timestamp.div_euclid(86400);
+        let seconds = timestamp.rem_euclid(86400) as u32;
+
+        // Convert days since 1970-01-01 to a civil date, see
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z.rem_euclid(146097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + i64::from(month <= 2);
+
+        Self {
+            year,
+            month,
+            day,
+            hour: seconds / 3600,
+            minute: seconds / 60 % 60,
+            second: seconds % 60,
+        }
+    }
+
+    pub(crate) fn from_system_time(time: SystemTime) -> Self {
+        let timestamp = match time.duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_secs() as i64,
+            Err(e) => -(e.duration().as_secs() as i64),
+        };
+        Self::from_unix_timestamp(timestamp)
+    }
+
+    /// E.g. `2021-03-04 05:06:07 UTC`
+    pub(crate) fn to_readable(&self) -> String {
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+
+    /// E.g. `20210304-050607`, suitable for file names
+    pub(crate) fn to_compact(&self) -> String {
+        format!(
+            "{:04}{:02}{:02}-{:02}{:02}{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+#[test]
+fn test_utc_date_time() {
+    assert_eq!(
+        UtcDateTime::from_unix_timestamp(0).to_compact(),
+        "19700101-000000"
+    );
+    assert_eq!(
+        UtcDateTime::from_unix_timestamp(951_825_600).to_compact(),
+        "20000229-120000"
+    );
+    assert_eq!(
+        UtcDateTime::from_unix_timestamp(1_614_834_367).to_compact(),
+        "20210304-050607"
+    );
+    assert_eq!(
+        UtcDateTime::from_unix_timestamp(-1).to_compact(),
+        "19691231-235959"
+    );
+    assert_eq!(
+        UtcDateTime::from_unix_timestamp(951_825_600).to_readable(),
+        "2000-02-29 12:00:00 UTC"
+    );
+}
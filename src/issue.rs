@@ -0,0 +1,263 @@
+//! Create a link that opens a new GitHub or GitLab issue with the report already filled in.
+//!
+//! ```
+//! use bugreport::{bugreport, collector::*, format::Markdown, issue::NewIssue};
+//!
+//! let report = bugreport!()
+//!     .info(SoftwareVersion::default())
+//!     .format::<Markdown>();
+//!
+//! let issue = NewIssue::new("https://github.com/sharkdp/bugreport", "Crash on startup")
+//!     .fallback_body("Please attach the bug report file.");
+//!
+//! if let Some(url) = issue.url(&report) {
+//!     println!("Please report this problem at {}", url);
+//! }
+//! ```
+
+/// Browsers and servers do not reliably handle longer URLs.
+const DEFAULT_MAX_LENGTH: usize = 8000;
+
+const TRUNCATION_NOTE: &str = "\n\n… (report truncated, please attach the full report)";
+
+/// The hosting service of the repository, which determines the format of the URL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Forge {
+    GitHub,
+    GitLab,
+}
+
+impl Forge {
+    /// Detect the hosting service from the host name of the repository URL.
+    pub fn detect(repository: &str) -> Option<Self> {
+        let host = repository
+            .split("://")
+            .nth(1)
+            .unwrap_or(repository)
+            .split('/')
+            .next()
+            .unwrap_or("");
+
+        if host == "github.com" || host.ends_with(".github.com") {
+            Some(Forge::GitHub)
+        } else if host.split('.').any(|part| part == "gitlab") {
+            Some(Forge::GitLab)
+        } else {
+            None
+        }
+    }
+}
+
+/// Builder for the URL of a new, pre-filled issue.
+///
+/// If the URL would be longer than the maximum length (8000 by default), the body is
+/// truncated, or replaced by the [fallback body](NewIssue::fallback_body) if one is given.
+pub struct NewIssue {
+    repository: String,
+    title: String,
+    forge: Option<Forge>,
+    max_length: usize,
+    fallback_body: Option<String>,
+}
+
+impl NewIssue {
+    /// `repository` is the web URL of the repository, e.g. `https://github.com/owner/repo`.
+    pub fn new(repository: &str, title: &str) -> Self {
+        let repository = repository.trim_end_matches('/');
+        let repository = repository.strip_suffix(".git").unwrap_or(repository);
+
+        Self {
+            repository: repository.into(),
+            title: title.into(),
+            forge: Forge::detect(repository),
+            max_length: DEFAULT_MAX_LENGTH,
+            fallback_body: None,
+        }
+    }
+
+    /// Set the hosting service, e.g. for a self-hosted GitLab instance whose host name does
+    /// not contain "gitlab".
+    pub fn forge(mut self, forge: Forge) -> Self {
+        self.forge = Some(forge);
+        self
+    }
+
+    /// Limit the length of the URL.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    /// Use this text as issue body (instead of a truncated report) if the report is too
+    /// long, e.g. to ask for the report file to be attached.
+    pub fn fallback_body(mut self, body: &str) -> Self {
+        self.fallback_body = Some(body.into());
+        self
+    }
+
+    /// Create the URL with the given issue body (usually a formatted report). Returns
+    /// `None` if the hosting service is unknown, or if even the URL without body is too long.
+    pub fn url(&self, body: &str) -> Option<String> {
+        let (path, title_key, body_key) = match self.forge? {
+            Forge::GitHub => ("issues/new", "title", "body"),
+            Forge::GitLab => ("-/issues/new", "issue[title]", "issue[description]"),
+        };
+
+        let prefix = format!(
+            "{}/{}?{}={}&{}=",
+            self.repository,
+            path,
+            title_key,
+            encode(&self.title),
+            body_key
+        );
+        let budget = self.max_length.checked_sub(prefix.len())?;
+
+        let encoded_body = encode(body);
+        let encoded_body = if encoded_body.len() <= budget {
+            encoded_body
+        } else {
+            match &self.fallback_body {
+                Some(fallback) => {
+                    let fallback = encode(fallback);
+                    if fallback.len() > budget {
+                        return None;
+                    }
+                    fallback
+                }
+                None => truncate(body, budget)?,
+            }
+        };
+
+        Some(prefix + &encoded_body)
+    }
+}
+
+/// Percent-encode as much of `body` as fits into `budget` bytes (including the note that
+/// the body has been truncated). Cuts at a line break, if possible.
+fn truncate(body: &str, budget: usize) -> Option<String> {
+    let note = encode(TRUNCATION_NOTE);
+    let budget = budget.checked_sub(note.len())?;
+
+    let mut encoded = String::new();
+    let mut last_line_break = None;
+    for c in body.chars() {
+        let encoded_char = encode(c.encode_utf8(&mut [0; 4]));
+        if encoded.len() + encoded_char.len() > budget {
+            break;
+        }
+        if c == '\n' {
+            last_line_break = Some(encoded.len());
+        }
+        encoded += &encoded_char;
+    }
+
+    if let Some(end) = last_line_break {
+        encoded.truncate(end);
+    }
+
+    Some(encoded + &note)
+}
+
+/// Percent-encode everything but unreserved characters (RFC 3986).
+fn encode(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            result.push(byte as char);
+        } else {
+            result += &format!("%{:02X}", byte);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_forge() {
+        assert_eq!(
+            Forge::detect("https://github.com/owner/repo"),
+            Some(Forge::GitHub)
+        );
+        assert_eq!(
+            Forge::detect("https://gitlab.com/group/project"),
+            Some(Forge::GitLab)
+        );
+        assert_eq!(
+            Forge::detect("https://gitlab.example.org/group/project"),
+            Some(Forge::GitLab)
+        );
+        assert_eq!(Forge::detect("https://example.org/github.com"), None);
+    }
+
+    #[test]
+    fn percent_encoding() {
+        assert_eq!(encode("a-z_0.9~"), "a-z_0.9~");
+        assert_eq!(encode("a b&c=d\n#ä"), "a%20b%26c%3Dd%0A%23%C3%A4");
+    }
+
+    #[test]
+    fn github() {
+        let issue = NewIssue::new("https://github.com/owner/repo.git", "Crash: \"42\"");
+
+        assert_eq!(
+            issue.url("#### Version\n\n1.0 & more").unwrap(),
+            "https://github.com/owner/repo/issues/new?title=Crash%3A%20%2242%22\
+             &body=%23%23%23%23%20Version%0A%0A1.0%20%26%20more"
+        );
+    }
+
+    #[test]
+    fn gitlab() {
+        let issue =
+            NewIssue::new("https://git.example.org/group/project/", "Crash").forge(Forge::GitLab);
+
+        assert_eq!(
+            issue.url("body").unwrap(),
+            "https://git.example.org/group/project/-/issues/new\
+             ?issue[title]=Crash&issue[description]=body"
+        );
+    }
+
+    #[test]
+    fn unknown_forge() {
+        assert_eq!(
+            NewIssue::new("https://example.org/repo", "Crash").url(""),
+            None
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let prefix = "https://github.com/o/r/issues/new?title=T&body=";
+        let note = encode(TRUNCATION_NOTE);
+        let issue =
+            NewIssue::new("https://github.com/o/r", "T").max_length(prefix.len() + note.len() + 12);
+
+        assert_eq!(
+            issue
+                .url(&format!("line1\nline2\n{}", "x".repeat(100)))
+                .unwrap(),
+            format!("{}line1{}", prefix, note)
+        );
+    }
+
+    #[test]
+    fn fallback() {
+        let issue = NewIssue::new("https://github.com/o/r", "T")
+            .max_length(80)
+            .fallback_body("see attached file");
+
+        assert_eq!(
+            issue.url(&"x".repeat(100)).unwrap(),
+            "https://github.com/o/r/issues/new?title=T&body=see%20attached%20file"
+        );
+        assert_eq!(
+            issue.url("short").unwrap(),
+            "https://github.com/o/r/issues/new?title=T&body=short"
+        );
+    }
+}
@@ -16,32 +16,113 @@
 //!     .print::<Markdown>();
 //! ```
 
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
 use std::result;
+use std::sync::Mutex;
+use std::thread;
 
+#[cfg(feature = "clap")]
+pub mod clap;
 pub mod collector;
+#[cfg(feature = "config")]
+pub mod config;
+mod consent;
+mod editor;
 pub mod format;
 mod helper;
+pub mod issue;
+pub mod panic;
+#[cfg(feature = "redaction")]
+pub mod redaction;
 pub mod report;
+pub mod trigger;
 
 use collector::{CollectionError, Collector};
 use format::Format;
-use report::{Report, ReportSection};
+#[cfg(feature = "redaction")]
+use redaction::Redaction;
+use report::{Report, ReportEntry, ReportSection};
+use trigger::EnvTrigger;
 
 pub(crate) type Result<T> = result::Result<T, CollectionError>;
 
-#[doc(hidden)]
+/// Information about the crate that the report is created for, passed to collectors and
+/// formats.
+#[derive(Debug)]
 pub struct CrateInfo<'a> {
     pkg_name: &'a str,
     pkg_version: &'a str,
     git_hash: Option<&'a str>,
 }
 
+impl<'a> CrateInfo<'a> {
+    /// The name of the crate, e.g. `bugreport`.
+    pub fn name(&self) -> &'a str {
+        self.pkg_name
+    }
+
+    /// The version of the crate, e.g. `0.6.0`.
+    pub fn version(&self) -> &'a str {
+        self.pkg_version
+    }
+
+    /// The hash of the git commit the crate has been built from, if known (see the
+    /// `git_hash` feature).
+    pub fn git_hash(&self) -> Option<&'a str> {
+        self.git_hash
+    }
+}
+
 /// The main struct for collecting bug report information.
 ///
 /// Use the [`bugreport`] macro to create one.
 pub struct BugReport<'a> {
     info: CrateInfo<'a>,
-    collectors: Vec<Box<dyn Collector>>,
+    collectors: Vec<ReportCollector>,
+    #[cfg(feature = "redaction")]
+    redaction: Option<Redaction>,
+    ask_for_consent: bool,
+    max_threads: usize,
+    failed_sections: FailedSections,
+    /// Questions (and hints) for the user, see [`BugReport::prompt`]
+    prompts: Vec<(String, String)>,
+}
+
+/// A collector of a [`BugReport`]. Only collectors that have been added with
+/// [`BugReport::parallel_info`] are known to be [`Send`] and may run on another thread.
+enum ReportCollector {
+    Local(Box<dyn Collector>),
+    Send(Box<dyn Collector + Send>),
+}
+
+impl ReportCollector {
+    fn get(&self) -> &dyn Collector {
+        match self {
+            ReportCollector::Local(collector) => collector,
+            ReportCollector::Send(collector) => collector,
+        }
+    }
+
+    fn get_mut(&mut self) -> &mut dyn Collector {
+        match self {
+            ReportCollector::Local(collector) => collector,
+            ReportCollector::Send(collector) => collector,
+        }
+    }
+}
+
+/// How sections whose collector failed are shown in the report.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FailedSections {
+    /// Show the error in place of the section content.
+    #[default]
+    Show,
+    /// Omit the section.
+    Hide,
+    /// Omit the section and list all errors in a final "Collection errors" section.
+    Summarize,
 }
 
 impl<'a> BugReport<'a> {
@@ -54,6 +135,12 @@ impl<'a> BugReport<'a> {
                 git_hash: None,
             },
             collectors: vec![],
+            #[cfg(feature = "redaction")]
+            redaction: None,
+            ask_for_consent: false,
+            max_threads: 1,
+            failed_sections: FailedSections::default(),
+            prompts: vec![],
         }
     }
 
@@ -64,38 +151,281 @@ impl<'a> BugReport<'a> {
 
     /// Add a [`Collector`] to the bug report.
     pub fn info<C: Collector + 'static>(mut self, collector: C) -> Self {
-        self.collectors.push(Box::new(collector));
+        self.collectors
+            .push(ReportCollector::Local(Box::new(collector)));
         self
     }
 
-    fn generate(&mut self) -> Report<'_> {
-        let mut sections = vec![];
+    /// Add a [`Collector`] that may run on another thread, in parallel to other collectors
+    /// (see [`BugReport::parallel`]). Behaves like [`BugReport::info`] otherwise.
+    pub fn parallel_info<C: Collector + Send + 'static>(mut self, collector: C) -> Self {
+        self.collectors
+            .push(ReportCollector::Send(Box::new(collector)));
+        self
+    }
+
+    /// Add all collectors that are listed in the given configuration, see [`config`].
+    #[cfg(feature = "config")]
+    pub fn config(mut self, config: config::Config) -> Self {
+        for collector in config.collectors {
+            self.collectors
+                .push(ReportCollector::Send(collector.into_collector()));
+        }
+        self
+    }
+
+    /// Add a question for the user, e.g. "What did you do?". Prompts are shown before all
+    /// collected sections, with an empty placeholder (showing `hint`) for the answer. Use
+    /// [`BugReport::edit`] to let the user fill them in.
+    pub fn prompt(mut self, question: &str, hint: &str) -> Self {
+        self.prompts.push((question.into(), hint.into()));
+        self
+    }
+
+    /// Add the prompts of a typical issue template (similar to `git bugreport`), so that
+    /// the report can be used as a complete issue description.
+    pub fn issue_template(self) -> Self {
+        self.prompt(
+            "What did you do?",
+            "Steps to reproduce the problem, e.g. the command that you ran",
+        )
+        .prompt("What did you expect to happen?", "Expected behavior")
+        .prompt(
+            "What happened instead?",
+            "Actual behavior, including any error messages",
+        )
+    }
+
+    /// Run up to `max_threads` collectors at the same time, e.g. to run several slow
+    /// [`CommandOutput`](collector::CommandOutput)s in parallel. The order of the sections
+    /// in the report is not affected.
+    ///
+    /// Only collectors that have been added with [`BugReport::parallel_info`] (and those of a
+    /// configuration file) are run in parallel, the others run one after another
+    /// on the current thread.
+    pub fn parallel(mut self, max_threads: usize) -> Self {
+        self.max_threads = max_threads.max(1);
+        self
+    }
+
+    /// Choose how sections are shown whose collector failed. Sections for which the
+    /// collector returns [`CollectionError::NotApplicable`] are always omitted.
+    pub fn failed_sections(mut self, failed_sections: FailedSections) -> Self {
+        self.failed_sections = failed_sections;
+        self
+    }
+
+    /// Anonymize the collected information before it is formatted, see [`Redaction`].
+    #[cfg(feature = "redaction")]
+    pub fn redact(mut self, redaction: Redaction) -> Self {
+        self.redaction = Some(redaction);
+        self
+    }
+
+    /// Before including a section, show a preview of it on the terminal and ask the user
+    /// whether it may be included. See [`BugReport::collect_with_consent`] for details.
+    pub fn ask_for_consent(mut self) -> Self {
+        self.ask_for_consent = true;
+        self
+    }
+
+    /// Run all collectors and return the resulting [`Report`] without formatting it.
+    pub fn collect(&mut self) -> Report {
+        if self.ask_for_consent {
+            let stdin = io::stdin();
+            self.collect_with_consent(stdin.lock(), io::stderr())
+        } else {
+            self.run_collectors()
+        }
+    }
 
-        for collector in &mut self.collectors {
-            let entry = collector
-                .collect(&self.info)
-                .unwrap_or_else(|e| e.to_entry());
+    /// Run all collectors and ask for permission to include each section. For every section,
+    /// its title and a preview are written to `output` and the answer is read from `input`.
+    /// Sections can be included, skipped or redacted (with the `redaction` feature). Skipped
+    /// sections, or all remaining ones if `input` ends, are marked as omitted by the user.
+    /// Redacted sections are rewritten by the redaction given to `BugReport::redact`, or by
+    /// the default one.
+    pub fn collect_with_consent<R: BufRead, W: Write>(&mut self, input: R, output: W) -> Report {
+        let mut report = self.run_collectors();
+        #[cfg(feature = "redaction")]
+        consent::ask(&mut report, input, output, self.redaction.as_ref());
+        #[cfg(not(feature = "redaction"))]
+        consent::ask(&mut report, input, output);
+        report
+    }
+
+    fn run_collectors(&mut self) -> Report {
+        let results = if self.max_threads > 1 {
+            collect_in_parallel(&mut self.collectors, &self.info, self.max_threads)
+        } else {
+            self.collectors
+                .iter_mut()
+                .map(|collector| collector.get_mut().collect(&self.info))
+                .collect()
+        };
+
+        let mut sections: Vec<_> = self
+            .prompts
+            .iter()
+            .map(|(question, hint)| ReportSection {
+                title: question.clone(),
+                entry: ReportEntry::Placeholder(hint.clone()),
+            })
+            .collect();
+        let mut errors = vec![];
+
+        for (collector, result) in self.collectors.iter().zip(results) {
+            let title = collector.get().description().into();
+            let entry = match (result, self.failed_sections) {
+                (Ok(entry), _) => entry,
+                (Err(CollectionError::NotApplicable), _) | (Err(_), FailedSections::Hide) => {
+                    continue;
+                }
+                (Err(e), FailedSections::Show) => e.to_entry(),
+                (Err(e), FailedSections::Summarize) => {
+                    errors.push(ReportEntry::key_value(title, e.to_string()));
+                    continue;
+                }
+            };
+            sections.push(ReportSection { title, entry });
+        }
+
+        if !errors.is_empty() {
             sections.push(ReportSection {
-                title: collector.description(),
-                entry,
+                title: "Collection errors".into(),
+                entry: ReportEntry::List(errors),
             });
         }
 
-        Report { sections }
+        #[allow(unused_mut)]
+        let mut report = Report {
+            crate_name: self.info.pkg_name.into(),
+            crate_version: self.info.pkg_version.into(),
+            git_hash: self.info.git_hash.map(Into::into),
+            sections,
+        };
+
+        #[cfg(feature = "redaction")]
+        if let Some(redaction) = &self.redaction {
+            redaction.apply(&mut report);
+        }
+
+        report
     }
 
     /// Assemble the bug report information using the given format.
     pub fn format<F: Format>(&mut self) -> String {
-        let mut format = F::default();
-        self.generate().format_as(&mut format)
+        self.collect().format::<F>()
+    }
+
+    /// Assemble the bug report information using the given (configured) format, e.g.
+    /// `Markdown::default().fold_sections(50)`.
+    pub fn format_with(&mut self, format: &mut impl Format) -> String {
+        self.collect().format_as(format)
+    }
+
+    /// Write the bug report information in the given format to `writer`. In contrast to
+    /// [`BugReport::format`], the formatted report is not assembled in memory first.
+    pub fn write_to<F: Format, W: Write>(&mut self, writer: W) -> io::Result<()> {
+        self.collect().write_to::<F, W>(writer)
     }
 
     /// Print the bug report information using the given format.
     pub fn print<F: Format>(&mut self) {
-        println!("{}", self.format::<F>());
+        let mut stdout = io::stdout().lock();
+        self.write_to::<F, _>(&mut stdout)
+            .and_then(|_| writeln!(stdout))
+            .expect("failed printing to stdout");
+    }
+
+    /// If the environment variable of the given trigger is set, write the bug report to a
+    /// file and return its path. Call this at startup (or before exiting) to let users
+    /// request a report without any command-line flags, like `FFREPORT` in `ffmpeg`.
+    pub fn write_if_triggered<F: Format>(
+        &mut self,
+        trigger: &EnvTrigger,
+    ) -> io::Result<Option<PathBuf>> {
+        match trigger.path(self.info.pkg_name) {
+            Some(path) => {
+                self.write_to::<F, _>(fs::File::create(&path)?)?;
+                Ok(Some(path))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Open the bug report in the user's editor (`$VISUAL`, `$EDITOR` or a platform
+    /// default, similar to `git bugreport`) so that additional information can be added.
+    /// Blocks until the editor is closed and returns the edited text.
+    pub fn edit<F: Format>(&mut self) -> io::Result<String> {
+        editor::edit(&self.format::<F>())
+    }
+
+    /// Like [`BugReport::edit`], but write the edited text to the given file.
+    pub fn edit_to_file<F: Format>(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.edit::<F>()?)
+    }
+}
+
+/// Run the collectors on up to `max_threads` threads and return their results in the
+/// original order. Collectors that are not [`Send`] run on the current thread.
+fn collect_in_parallel(
+    collectors: &mut [ReportCollector],
+    crate_info: &CrateInfo,
+    max_threads: usize,
+) -> Vec<Result<ReportEntry>> {
+    let mut results = Vec::new();
+    results.resize_with(collectors.len(), || None);
+    let results = Mutex::new(results);
+
+    let mut local = vec![];
+    let mut shared = vec![];
+    for (i, collector) in collectors.iter_mut().enumerate() {
+        match collector {
+            ReportCollector::Local(collector) => local.push((i, collector)),
+            ReportCollector::Send(collector) => shared.push((i, collector)),
+        }
     }
+
+    let workers = (max_threads - 1).min(shared.len());
+    let queue = Mutex::new(shared.into_iter());
+    let run_queue = || {
+        loop {
+            let Some((i, collector)) = queue.lock().unwrap().next() else {
+                break;
+            };
+
+            let result = collector.collect(crate_info);
+            results.lock().unwrap()[i] = Some(result);
+        }
+    };
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(run_queue);
+        }
+
+        for (i, collector) in local {
+            let result = collector.collect(crate_info);
+            results.lock().unwrap()[i] = Some(result);
+        }
+        run_queue();
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every collector has been run"))
+        .collect()
 }
 
+/// Re-export for the patterns of `redaction::Redaction` and for
+/// [`collector::EnvironmentVariables::regex`], so that dependent projects do not have to
+/// manually depend on the regex crate.
+#[cfg(feature = "regex")]
+pub use regex::Regex;
+
 /// Re-export so dependent project does not have to manually depend on git-version crate
 #[cfg(feature = "git_hash")]
 pub use git_version::git_version;
@@ -158,4 +488,26 @@ mod tests {
              ```\n\n"
         );
     }
+
+    #[test]
+    fn collect() {
+        use super::BugReport;
+        use crate::collector::*;
+        use crate::report::{ReportEntry, ReportSection};
+
+        let report = BugReport::from_name_and_version("dummy", "0.1")
+            .info(SoftwareVersion::custom("0.2"))
+            .collect();
+
+        assert_eq!(report.crate_name, "dummy");
+        assert_eq!(report.crate_version, "0.1");
+        assert_eq!(report.git_hash, None);
+        assert_eq!(
+            report.sections,
+            vec![ReportSection {
+                title: "Software version".into(),
+                entry: ReportEntry::Text("dummy 0.2".into()),
+            }]
+        );
+    }
 }
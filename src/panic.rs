@@ -0,0 +1,119 @@
+//! Write a bug report when the program panics.
+//!
+//! ```no_run
+//! use bugreport::{bugreport, collector::*, format::Markdown};
+//!
+//! bugreport::panic::install::<Markdown>(|| {
+//!     bugreport!()
+//!         .info(SoftwareVersion::default())
+//!         .info(OperatingSystem::default())
+//!         .info(CommandLine::default())
+//! });
+//! ```
+
+use std::backtrace::Backtrace;
+use std::io;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::format::Format;
+use crate::helper::{UtcDateTime, create_private_temp_file};
+use crate::report::{Code, ReportEntry};
+use crate::{BugReport, Collector, CrateInfo, ReportCollector, Result};
+
+/// Register a panic hook that writes a bug report to a new temporary file (only readable by
+/// the current user) and prints its path to stderr. The report is created by `builder` and
+/// contains an additional section with the panic message, its location, the name of the
+/// thread and a backtrace.
+///
+/// The previously registered hook (e.g. the default one that prints the panic message) is
+/// still called before the report is generated. Note that a panic inside one of the
+/// collectors aborts the program.
+pub fn install<F: Format>(builder: impl Fn() -> BugReport<'static> + Send + Sync + 'static) {
+    let previous_hook = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        previous_hook(info);
+
+        let mut report = builder();
+        report.collectors.insert(
+            0,
+            ReportCollector::Local(Box::new(PanicInformation::new(info))),
+        );
+
+        match write_report::<F>(&mut report) {
+            Ok(path) => eprintln!(
+                "\nA bug report has been written to '{}'.\n\
+                 Please attach it when reporting this problem.",
+                path.to_string_lossy()
+            ),
+            Err(e) => eprintln!("\nCould not write bug report: {}", e),
+        }
+    }));
+}
+
+fn write_report<F: Format>(report: &mut BugReport) -> io::Result<PathBuf> {
+    let (file, path) = create_private_temp_file(
+        &format!(
+            "{}-panic-{}",
+            report.info.pkg_name,
+            UtcDateTime::from_system_time(SystemTime::now()).to_compact()
+        ),
+        "log",
+    )?;
+
+    report.write_to::<F, _>(file)?;
+
+    Ok(path)
+}
+
+/// Information about the panic, captured when the hook is called.
+struct PanicInformation {
+    message: String,
+    location: Option<String>,
+    thread: String,
+    backtrace: String,
+}
+
+impl PanicInformation {
+    fn new(info: &PanicHookInfo) -> Self {
+        let payload = info.payload();
+        let message = if let Some(s) = payload.downcast_ref::<&str>() {
+            (*s).to_owned()
+        } else if let Some(s) = payload.downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "<unknown panic payload>".into()
+        };
+
+        Self {
+            message,
+            location: info
+                .location()
+                .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+            thread: std::thread::current().name().unwrap_or("<unnamed>").into(),
+            backtrace: Backtrace::force_capture().to_string(),
+        }
+    }
+}
+
+impl Collector for PanicInformation {
+    fn description(&self) -> &str {
+        "Panic"
+    }
+
+    fn collect(&mut self, _: &CrateInfo) -> Result<ReportEntry> {
+        Ok(ReportEntry::Concat(vec![
+            ReportEntry::List(vec![
+                ReportEntry::key_value("Message", &self.message),
+                ReportEntry::key_value("Location", self.location.as_deref().unwrap_or("unknown")),
+                ReportEntry::key_value("Thread", &self.thread),
+            ]),
+            ReportEntry::Code(Code {
+                language: None,
+                code: self.backtrace.trim_end().into(),
+            }),
+        ]))
+    }
+}
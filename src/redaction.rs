@@ -0,0 +1,283 @@
+//! Anonymization of collected information before it is formatted.
+//!
+//! ```
+//! use bugreport::{Regex, bugreport, collector::*, format::Markdown, redaction::Redaction};
+//!
+//! bugreport!()
+//!     .info(CommandLine::default())
+//!     .info(EnvironmentVariables::list(&["HOME", "PATH"]))
+//!     .redact(Redaction::default().pattern(Regex::new(r"ghp_[A-Za-z0-9]+").unwrap()))
+//!     .print::<Markdown>();
+//! ```
+
+use crate::Regex;
+use crate::report::{Report, ReportEntry};
+
+/// A set of rules that rewrite every string in a [`Report`].
+///
+/// User-supplied patterns are applied first, followed by the builtin rules for the home
+/// directory, the username and the hostname (in this order).
+///
+/// The default redaction replaces the home directory by `~`, the username by `<user>` and
+/// the hostname by `<hostname>`. Use [`Redaction::none`] to start without any rules.
+pub struct Redaction {
+    patterns: Vec<Rule>,
+    builtins: Vec<Rule>,
+}
+
+struct Rule {
+    pattern: Regex,
+    replacement: String,
+}
+
+impl Default for Redaction {
+    fn default() -> Self {
+        Self::none().home_directory().username().hostname()
+    }
+}
+
+impl Redaction {
+    /// A redaction without any rules.
+    pub fn none() -> Self {
+        Self {
+            patterns: vec![],
+            builtins: vec![],
+        }
+    }
+
+    /// Replace the home directory of the current user by `~`.
+    pub fn home_directory(self) -> Self {
+        let home = std::env::var("HOME")
+            .or_else(|_| std::env::var("USERPROFILE"))
+            .unwrap_or_default();
+        self.with_home_directory(&home)
+    }
+
+    /// Replace the name of the current user by `<user>`.
+    ///
+    /// Only whole words are replaced, so a user called `ann` does not affect `annotation`.
+    pub fn username(self) -> Self {
+        let username = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .or_else(|_| std::env::var("LOGNAME"))
+            .unwrap_or_default();
+        self.with_word(&username, "<user>")
+    }
+
+    /// Replace the hostname of the current machine by `<hostname>`.
+    pub fn hostname(self) -> Self {
+        #[cfg(feature = "collector_operating_system")]
+        let hostname = sysinfo::System::host_name();
+        #[cfg(not(feature = "collector_operating_system"))]
+        let hostname: Option<String> = None;
+
+        let hostname = hostname
+            .or_else(|| std::env::var("HOSTNAME").ok())
+            .or_else(|| std::env::var("COMPUTERNAME").ok())
+            .unwrap_or_default();
+        self.with_word(&hostname, "<hostname>")
+    }
+
+    /// Replace all matches of the given pattern (e.g. access tokens, emails or IP addresses)
+    /// by `<redacted>`.
+    pub fn pattern(self, pattern: Regex) -> Self {
+        self.replace(pattern, "<redacted>")
+    }
+
+    /// Replace all matches of the given pattern by `replacement`, which may refer to capture
+    /// groups like `$1`.
+    pub fn replace(mut self, pattern: Regex, replacement: &str) -> Self {
+        self.patterns.push(Rule {
+            pattern,
+            replacement: replacement.into(),
+        });
+        self
+    }
+
+    /// Apply all rules to every entry of the given report.
+    pub fn apply(&self, report: &mut Report) {
+        for section in &mut report.sections {
+            self.apply_to_string(&mut section.title);
+            self.apply_to_entry(&mut section.entry);
+        }
+    }
+
+    pub(crate) fn apply_to_entry(&self, entry: &mut ReportEntry) {
+        use ReportEntry::*;
+
+        match entry {
+            Text(content) | Error(content) => self.apply_to_string(content),
+            Code(c) => self.apply_to_string(&mut c.code),
+            List(entries) | Concat(entries) => {
+                for entry in entries {
+                    self.apply_to_entry(entry);
+                }
+            }
+            KeyValue { key, value } => {
+                self.apply_to_string(key);
+                self.apply_to_string(value);
+            }
+            Table { headers, rows } => {
+                for cell in headers.iter_mut().chain(rows.iter_mut().flatten()) {
+                    self.apply_to_string(cell);
+                }
+            }
+            Section { title, body } => {
+                self.apply_to_string(title);
+                self.apply_to_entry(body);
+            }
+            Placeholder(_) => {}
+        }
+    }
+
+    fn apply_to_string(&self, s: &mut String) {
+        for rule in self.patterns.iter().chain(&self.builtins) {
+            if let std::borrow::Cow::Owned(replaced) =
+                rule.pattern.replace_all(s, rule.replacement.as_str())
+            {
+                *s = replaced;
+            }
+        }
+    }
+
+    fn with_home_directory(mut self, home: &str) -> Self {
+        let home = home.trim_end_matches(['/', '\\']);
+
+        // An empty home (or `/`) would match far too much.
+        if !home.is_empty() {
+            // Make sure that `/home/ann` does not match `/home/annette`
+            let boundary = if home.ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+                r"\b"
+            } else {
+                ""
+            };
+
+            self.builtins.push(Rule {
+                pattern: Regex::new(&format!("{}{}", regex::escape(home), boundary))
+                    .expect("escaped regex is valid"),
+                replacement: "~".into(),
+            });
+        }
+        self
+    }
+
+    fn with_word(mut self, word: &str, replacement: &str) -> Self {
+        if !word.is_empty() {
+            self.builtins.push(Rule {
+                pattern: Regex::new(&format!(r"\b{}\b", regex::escape(word)))
+                    .expect("escaped regex is valid"),
+                replacement: replacement.into(),
+            });
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{Code, ReportSection};
+
+    fn redact(redaction: &Redaction, entry: ReportEntry) -> ReportEntry {
+        let mut report = Report {
+            crate_name: "dummy".into(),
+            crate_version: "0.1".into(),
+            git_hash: None,
+            sections: vec![ReportSection {
+                title: "Section".into(),
+                entry,
+            }],
+        };
+        redaction.apply(&mut report);
+        report.sections.pop().unwrap().entry
+    }
+
+    #[test]
+    fn builtins() {
+        let redaction = Redaction::none()
+            .with_home_directory("/home/ann/")
+            .with_word("ann", "<user>")
+            .with_word("box", "<hostname>");
+
+        assert_eq!(
+            redact(
+                &redaction,
+                ReportEntry::List(vec![
+                    ReportEntry::Text("/home/ann/.config/app.toml".into()),
+                    ReportEntry::Text("ann@box: annotation in /home/annette".into()),
+                ])
+            ),
+            ReportEntry::List(vec![
+                ReportEntry::Text("~/.config/app.toml".into()),
+                ReportEntry::Text("<user>@<hostname>: annotation in /home/annette".into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn empty_builtins_are_ignored() {
+        let redaction = Redaction::none()
+            .with_home_directory("/")
+            .with_word("", "<user>");
+
+        assert_eq!(
+            redact(&redaction, ReportEntry::Text("/usr/bin".into())),
+            ReportEntry::Text("/usr/bin".into())
+        );
+    }
+
+    #[test]
+    fn patterns_before_builtins() {
+        let redaction = Redaction::none()
+            .with_word("ann", "<user>")
+            .pattern(Regex::new(r"[a-z]+@example\.com").unwrap())
+            .replace(Regex::new(r"token=(\w)\w*").unwrap(), "token=$1…");
+
+        assert_eq!(
+            redact(
+                &redaction,
+                ReportEntry::Code(Code {
+                    language: None,
+                    code: "ann@example.com token=abcdef ann".into(),
+                })
+            ),
+            ReportEntry::Code(Code {
+                language: None,
+                code: "<redacted> token=a… <user>".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn all_strings() {
+        let redaction = Redaction::none().with_word("ann", "<user>");
+
+        assert_eq!(
+            redact(
+                &redaction,
+                ReportEntry::Concat(vec![
+                    ReportEntry::key_value("ann", "ann"),
+                    ReportEntry::Table {
+                        headers: vec!["ann".into()],
+                        rows: vec![vec!["ann".into()]],
+                    },
+                    ReportEntry::Section {
+                        title: "ann".into(),
+                        body: Box::new(ReportEntry::Error("ann".into())),
+                    },
+                ])
+            ),
+            ReportEntry::Concat(vec![
+                ReportEntry::key_value("<user>", "<user>"),
+                ReportEntry::Table {
+                    headers: vec!["<user>".into()],
+                    rows: vec![vec!["<user>".into()]],
+                },
+                ReportEntry::Section {
+                    title: "<user>".into(),
+                    body: Box::new(ReportEntry::Error("<user>".into())),
+                },
+            ])
+        );
+    }
+}
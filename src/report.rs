@@ -1,43 +1,283 @@
-//! Defines the document structure of the report. Only needed for custom collectors.
+//! Defines the document structure of the report. Needed for custom collectors and formats,
+//! or to process a collected [`Report`] yourself.
 
-use crate::format::Format;
+use std::io::{self, BufWriter, Write};
 
-#[derive(Debug)]
+use crate::CrateInfo;
+use crate::format::{Format, write_to_string};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Code {
     pub language: Option<String>,
     pub code: String,
 }
 
-#[derive(Debug)]
+/// With the `serde` feature, entries are (de)serialized in the same format as the one
+/// written by `format::Json`, e.g. `{"type":"text","text":"…"}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ReportEntry {
     Text(String),
     Code(Code),
     List(Vec<ReportEntry>),
     Concat(Vec<ReportEntry>),
+    /// A named value, e.g. `Profile: debug`. Consecutive key/value pairs in a
+    /// [`ReportEntry::List`] are aligned by formats that support it.
+    KeyValue {
+        key: String,
+        value: String,
+    },
+    Table {
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+    /// Information that could not be collected, see
+    /// [`CollectionError`](crate::collector::CollectionError).
+    Error(String),
+    /// Space for the user to fill in, with a hint what to write, see
+    /// [`BugReport::prompt`](crate::BugReport::prompt).
+    Placeholder(String),
+    /// A nested section with its own heading, e.g. as produced by
+    /// [`Group`](crate::collector::Group).
+    Section {
+        title: String,
+        body: Box<ReportEntry>,
+    },
 }
 
-#[derive(Debug)]
-pub(crate) struct ReportSection<'a> {
-    pub(crate) title: &'a str,
-    pub(crate) entry: ReportEntry,
+/// A titled part of the report, produced by a single collector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReportSection {
+    pub title: String,
+    pub entry: ReportEntry,
 }
 
-impl ReportEntry {}
+impl ReportEntry {
+    pub fn key_value<K: Into<String>, V: Into<String>>(key: K, value: V) -> Self {
+        ReportEntry::KeyValue {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
 
-#[derive(Debug)]
-pub(crate) struct Report<'a> {
-    pub(crate) sections: Vec<ReportSection<'a>>,
+/// The collected bug report information, independent of any output format.
+///
+/// Use [`BugReport::collect`](crate::BugReport::collect) to obtain one. With the `serde`
+/// feature, it can be (de)serialized in the format of `format::Json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub crate_name: String,
+    pub crate_version: String,
+    pub git_hash: Option<String>,
+    pub sections: Vec<ReportSection>,
 }
 
-impl Report<'_> {
+impl Report {
+    /// Assemble the report using the given format.
+    pub fn format<F: Format>(&self) -> String {
+        self.format_as(&mut F::default())
+    }
+
     pub fn format_as(&self, format: &mut impl Format) -> String {
-        let mut result = String::new();
+        write_to_string(|w| self.write_as(format, w))
+    }
+
+    /// Write the report in the given format, without assembling it in memory first.
+    pub fn write_to<F: Format, W: Write>(&self, writer: W) -> io::Result<()> {
+        self.write_as(&mut F::default(), writer)
+    }
+
+    pub fn write_as<W: Write>(&self, format: &mut impl Format, writer: W) -> io::Result<()> {
+        let crate_info = CrateInfo {
+            pkg_name: &self.crate_name,
+            pkg_version: &self.crate_version,
+            git_hash: self.git_hash.as_deref(),
+        };
+
+        let mut writer = BufWriter::new(writer);
+        let w: &mut dyn Write = &mut writer;
+
+        format.write_header(w, &crate_info)?;
         for section in &self.sections {
-            result += &format.format_section(section.title);
-            result += &format.format_entry(&section.entry);
-            result += "\n";
+            format.write_section(w, &section.title)?;
+            format.write_entry(w, &section.entry)?;
+            format.write_section_end(w)?;
         }
+        format.write_footer(w)?;
+
+        writer.flush()
+    }
+}
 
-        result
+/// (De)serialize the report in the same format as `format::Json`, through
+/// intermediate types whose derived representation matches it.
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{Code, Report, ReportEntry, ReportSection};
+
+    #[derive(Serialize)]
+    #[serde(tag = "type", rename_all = "snake_case")]
+    enum EntryRef<'a> {
+        Text {
+            text: &'a str,
+        },
+        Code {
+            language: Option<&'a str>,
+            code: &'a str,
+        },
+        List {
+            entries: &'a [ReportEntry],
+        },
+        Concat {
+            entries: &'a [ReportEntry],
+        },
+        KeyValue {
+            key: &'a str,
+            value: &'a str,
+        },
+        Table {
+            headers: &'a [String],
+            rows: &'a [Vec<String>],
+        },
+        Error {
+            message: &'a str,
+        },
+        Placeholder {
+            hint: &'a str,
+        },
+        Section {
+            title: &'a str,
+            body: &'a ReportEntry,
+        },
+    }
+
+    #[derive(Deserialize)]
+    #[serde(tag = "type", rename_all = "snake_case")]
+    enum Entry {
+        Text {
+            text: String,
+        },
+        Code {
+            language: Option<String>,
+            code: String,
+        },
+        List {
+            entries: Vec<ReportEntry>,
+        },
+        Concat {
+            entries: Vec<ReportEntry>,
+        },
+        KeyValue {
+            key: String,
+            value: String,
+        },
+        Table {
+            headers: Vec<String>,
+            rows: Vec<Vec<String>>,
+        },
+        Error {
+            message: String,
+        },
+        Placeholder {
+            hint: String,
+        },
+        Section {
+            title: String,
+            body: Box<ReportEntry>,
+        },
+    }
+
+    impl Serialize for ReportEntry {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let entry = match self {
+                ReportEntry::Text(text) => EntryRef::Text { text },
+                ReportEntry::Code(c) => EntryRef::Code {
+                    language: c.language.as_deref(),
+                    code: &c.code,
+                },
+                ReportEntry::List(entries) => EntryRef::List { entries },
+                ReportEntry::Concat(entries) => EntryRef::Concat { entries },
+                ReportEntry::KeyValue { key, value } => EntryRef::KeyValue { key, value },
+                ReportEntry::Table { headers, rows } => EntryRef::Table { headers, rows },
+                ReportEntry::Error(message) => EntryRef::Error { message },
+                ReportEntry::Placeholder(hint) => EntryRef::Placeholder { hint },
+                ReportEntry::Section { title, body } => EntryRef::Section { title, body },
+            };
+            entry.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ReportEntry {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            Ok(match Entry::deserialize(deserializer)? {
+                Entry::Text { text } => ReportEntry::Text(text),
+                Entry::Code { language, code } => ReportEntry::Code(Code { language, code }),
+                Entry::List { entries } => ReportEntry::List(entries),
+                Entry::Concat { entries } => ReportEntry::Concat(entries),
+                Entry::KeyValue { key, value } => ReportEntry::KeyValue { key, value },
+                Entry::Table { headers, rows } => ReportEntry::Table { headers, rows },
+                Entry::Error { message } => ReportEntry::Error(message),
+                Entry::Placeholder { hint } => ReportEntry::Placeholder(hint),
+                Entry::Section { title, body } => ReportEntry::Section { title, body },
+            })
+        }
+    }
+
+    #[derive(Serialize)]
+    struct CrateRef<'a> {
+        name: &'a str,
+        version: &'a str,
+        git_hash: Option<&'a str>,
+    }
+
+    #[derive(Serialize)]
+    struct ReportRef<'a> {
+        #[serde(rename = "crate")]
+        crate_info: CrateRef<'a>,
+        sections: &'a [ReportSection],
+    }
+
+    #[derive(Deserialize)]
+    struct Crate {
+        name: String,
+        version: String,
+        git_hash: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    struct ReportRepr {
+        #[serde(rename = "crate")]
+        crate_info: Crate,
+        sections: Vec<ReportSection>,
+    }
+
+    impl Serialize for Report {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            ReportRef {
+                crate_info: CrateRef {
+                    name: &self.crate_name,
+                    version: &self.crate_version,
+                    git_hash: self.git_hash.as_deref(),
+                },
+                sections: &self.sections,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Report {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let report = ReportRepr::deserialize(deserializer)?;
+            Ok(Report {
+                crate_name: report.crate_info.name,
+                crate_version: report.crate_info.version,
+                git_hash: report.crate_info.git_hash,
+                sections: report.sections,
+            })
+        }
     }
 }
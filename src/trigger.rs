@@ -0,0 +1,151 @@
+//! Write a report to a file when an environment variable is set, similar to `FFREPORT` in
+//! `ffmpeg`.
+//!
+//! ```no_run
+//! use bugreport::{bugreport, collector::*, format::Markdown, trigger::EnvTrigger};
+//!
+//! // Writes e.g. `myapp-20210304-050607.log` if MYAPP_REPORT is set
+//! bugreport!()
+//!     .info(SoftwareVersion::default())
+//!     .info(OperatingSystem::default())
+//!     .write_if_triggered::<Markdown>(&EnvTrigger::new("MYAPP_REPORT"))
+//!     .expect("could not write bug report");
+//! ```
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::helper::UtcDateTime;
+
+/// An environment variable that triggers writing a report to a file.
+///
+/// The file name is generated from a template in which `%p` is replaced by the program
+/// name, `%t` by the current time (UTC, e.g. `20210304-050607`) and `%%` by a plain `%`.
+/// The default template is `%p-%t.log`. Like with `FFREPORT`, the template can also be
+/// overridden by setting the variable to `file=<template>`.
+pub struct EnvTrigger {
+    var: OsString,
+    directory: PathBuf,
+    template: String,
+}
+
+impl EnvTrigger {
+    /// Trigger on the given environment variable. The file is written to the current
+    /// working directory unless [`EnvTrigger::directory`] is used.
+    pub fn new(var: &str) -> Self {
+        Self {
+            var: var.into(),
+            directory: PathBuf::from("."),
+            template: "%p-%t.log".into(),
+        }
+    }
+
+    /// Write the file to the given directory.
+    pub fn directory<P: AsRef<Path>>(mut self, directory: P) -> Self {
+        self.directory = directory.as_ref().to_path_buf();
+        self
+    }
+
+    /// Use the given template for the file name.
+    pub fn file_template(mut self, template: &str) -> Self {
+        self.template = template.into();
+        self
+    }
+
+    /// The path to write the report to, or `None` if the variable is not set.
+    pub(crate) fn path(&self, program: &str) -> Option<PathBuf> {
+        let value = std::env::var_os(&self.var)?;
+        let value = value.to_string_lossy();
+
+        let template = value
+            .split(':')
+            .find_map(|option| option.strip_prefix("file="))
+            .unwrap_or(&self.template);
+
+        Some(self.directory.join(expand_template(
+            template,
+            program,
+            &UtcDateTime::from_system_time(SystemTime::now()),
+        )))
+    }
+}
+
+fn expand_template(template: &str, program: &str, time: &UtcDateTime) -> String {
+    let mut result = String::new();
+    let mut chars = template.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('p') => result += program,
+            Some('t') => result += &time.to_compact(),
+            Some('%') => result.push('%'),
+            Some(other) => {
+                result.push('%');
+                result.push(other);
+            }
+            None => result.push('%'),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template() {
+        let time = UtcDateTime::from_unix_timestamp(1_614_834_367);
+
+        assert_eq!(
+            expand_template("%p-%t.log", "myapp", &time),
+            "myapp-20210304-050607.log"
+        );
+        assert_eq!(expand_template("100%%-%x-%", "myapp", &time), "100%-%x-%");
+    }
+
+    #[test]
+    #[cfg(feature = "format_markdown")]
+    fn write_if_triggered() -> std::io::Result<()> {
+        use crate::BugReport;
+        use crate::collector::SoftwareVersion;
+        use crate::format::Markdown;
+
+        let dir = tempfile::tempdir()?;
+        let trigger = EnvTrigger::new("BUGREPORT_TEST_TRIGGER")
+            .directory(dir.path())
+            .file_template("report-%p.md");
+
+        let mut report =
+            BugReport::from_name_and_version("dummy", "0.1").info(SoftwareVersion::default());
+
+        assert_eq!(report.write_if_triggered::<Markdown>(&trigger)?, None);
+
+        unsafe {
+            std::env::set_var("BUGREPORT_TEST_TRIGGER", "1");
+        }
+
+        let path = report.write_if_triggered::<Markdown>(&trigger)?;
+        assert_eq!(path, Some(dir.path().join("report-dummy.md")));
+        assert_eq!(
+            std::fs::read_to_string(path.unwrap())?,
+            "#### Software version\n\ndummy 0.1\n\n"
+        );
+
+        unsafe {
+            std::env::set_var("BUGREPORT_TEST_TRIGGER", "level=32:file=custom.log");
+        }
+
+        let path = report.write_if_triggered::<Markdown>(&trigger)?;
+        assert_eq!(path, Some(dir.path().join("custom.log")));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "cli")]
+
+use std::fs;
+use std::process::Command;
+
+use pretty_assertions::assert_eq;
+
+fn bugreport() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_bugreport"))
+}
+
+const MANIFEST: &str = r#"
+name = "mytool"
+version = "1.2.3"
+
+[[collector]]
+type = "software_version"
+
+[[collector]]
+type = "file_content"
+title = "Config"
+path = "config.txt"
+"#;
+
+#[test]
+fn print_report() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("manifest.toml"), MANIFEST).unwrap();
+    fs::write(dir.path().join("config.txt"), "verbose = true\n").unwrap();
+
+    let output = bugreport()
+        .arg("manifest.toml")
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "#### Software version\n\
+         \n\
+         mytool 1.2.3\n\
+         \n\
+         #### Config\n\
+         \n\
+         ```\n\
+         verbose = true\n\
+         ```\n\n"
+    );
+}
+
+#[test]
+fn write_report_to_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let manifest = dir.path().join("manifest.toml");
+    fs::write(&manifest, format!("format = \"plaintext\"\n{}", MANIFEST)).unwrap();
+
+    let output = bugreport()
+        .args(["--format", "json", "--output"])
+        .arg(dir.path().join("report.json"))
+        .arg(&manifest)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+
+    let report = fs::read_to_string(dir.path().join("report.json")).unwrap();
+    assert!(report.starts_with("{\"crate\":{\"name\":\"mytool\",\"version\":\"1.2.3\""));
+}
+
+#[test]
+fn invalid_manifest() {
+    let dir = tempfile::tempdir().unwrap();
+    let manifest = dir.path().join("manifest.toml");
+    fs::write(
+        &manifest,
+        "name = \"mytool\"\n[[collector]]\ntype = \"unknown\"\n",
+    )
+    .unwrap();
+
+    let output = bugreport().arg(&manifest).output().unwrap();
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .starts_with("bugreport: error: invalid manifest:")
+    );
+}
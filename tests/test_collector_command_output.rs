@@ -0,0 +1,238 @@
+#![cfg(all(unix, feature = "format_markdown"))]
+
+use std::time::{Duration, Instant};
+
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+use bugreport::{BugReport, FailedSections, collector::CommandOutput, format::Markdown};
+
+fn report(collector: CommandOutput) -> String {
+    BugReport::from_name_and_version("dummy", "0.1")
+        .info(collector)
+        .format::<Markdown>()
+}
+
+#[test]
+fn output_and_exit_code() {
+    let actual = report(CommandOutput::new(
+        "Output",
+        "sh",
+        &["-c", "echo out; echo err >&2; exit 3"],
+    ));
+
+    let expected = "#### Output
+
+```
+> sh -c 'echo out; echo err >&2; exit 3'
+out
+err
+```
+Command failed with exit code 3.
+
+";
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn timeout() {
+    let start = Instant::now();
+    let actual = report(
+        CommandOutput::new("Slow", "sh", &["-c", "echo started; exec sleep 10"])
+            .timeout(Duration::from_millis(500)),
+    );
+
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    let expected = "#### Slow
+
+```
+> sh -c 'echo started; exec sleep 10'
+started
+```
+Command timed out after 0.5 s.
+
+";
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn timeout_with_background_process() {
+    // The background process keeps stdout open after the command itself has exited
+    let start = Instant::now();
+    let actual = report(
+        CommandOutput::new("Background", "sh", &["-c", "sleep 10 & echo hi"])
+            .timeout(Duration::from_millis(500)),
+    );
+
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    let expected = "#### Background
+
+```
+> sh -c 'sleep 10 & echo hi'
+hi
+```
+
+";
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn timeout_without_output() {
+    let actual = BugReport::from_name_and_version("dummy", "0.1")
+        .info(CommandOutput::new("Silent", "sleep", &["10"]).timeout(Duration::from_millis(200)))
+        .failed_sections(FailedSections::Summarize)
+        .format::<Markdown>();
+
+    let expected = "#### Collection errors
+
+- Silent: Timed out after 0.2 s
+
+";
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn max_output_bytes() {
+    let actual = report(
+        CommandOutput::new("Long", "sh", &["-c", "echo 0123456789; echo abcdef"])
+            .max_output_bytes(5),
+    );
+
+    let expected = "#### Long
+
+```
+> sh -c 'echo 0123456789; echo abcdef'
+01234
+[… output truncated after 5 bytes]
+```
+
+";
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn stdin_env_and_current_dir() -> Result<(), std::io::Error> {
+    let dir = tempdir()?;
+    std::fs::write(dir.path().join("file.txt"), "from file\n")?;
+
+    let actual = report(
+        CommandOutput::new("Input", "sh", &["-c", "cat; cat file.txt; echo $GREETING"])
+            .stdin("from stdin\n")
+            .env("GREETING", "hello")
+            .current_dir(dir.path()),
+    );
+
+    let expected = "#### Input
+
+```
+> sh -c 'cat; cat file.txt; echo $GREETING'
+from stdin
+from file
+hello
+```
+
+";
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+fn stdin_is_closed_by_default() {
+    let actual = report(
+        CommandOutput::new("Read", "sh", &["-c", "cat; echo done"]).timeout(Duration::from_secs(5)),
+    );
+
+    let expected = "#### Read
+
+```
+> sh -c 'cat; echo done'
+done
+```
+
+";
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn separate_streams() {
+    let actual = report(
+        CommandOutput::new("Streams", "sh", &["-c", "echo out; echo err >&2"]).separate_streams(),
+    );
+
+    let expected = "#### Streams
+
+```
+> sh -c 'echo out; echo err >&2'
+```
+stdout:
+```
+out
+```
+stderr:
+```
+err
+```
+
+";
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn interleave_streams() {
+    let actual = report(
+        CommandOutput::new(
+            "Streams",
+            "sh",
+            &[
+                "-c",
+                "echo one; sleep 0.2; echo two >&2; sleep 0.2; echo three",
+            ],
+        )
+        .interleave_streams(),
+    );
+
+    let expected = "#### Streams
+
+```
+> sh -c 'echo one; sleep 0.2; echo two >&2; sleep 0.2; echo three'
+one
+two
+three
+```
+
+";
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn invalid_utf8() {
+    let actual = report(CommandOutput::new(
+        "Binary",
+        "sh",
+        &["-c", r"printf 'valid \377 invalid\n'"],
+    ));
+
+    let expected = "#### Binary
+
+```
+> sh -c 'printf '\\''valid \\377 invalid\\n'\\'''
+valid \u{FFFD} invalid
+```
+Output is not valid UTF-8, invalid sequences were replaced by '\u{FFFD}'.
+
+";
+
+    assert_eq!(expected, actual);
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "format_markdown")]
+
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+use bugreport::{BugReport, collector::CurrentWorkingDirectory, format::Markdown};
+
+#[test]
+fn current_working_directory() -> Result<(), std::io::Error> {
+    let dir = tempdir()?;
+    std::fs::write(dir.path().join("file.txt"), "This is a file")?;
+    std::fs::create_dir_all(dir.path().join("repo").join(".git"))?;
+    std::fs::create_dir(dir.path().join("repo").join("src"))?;
+
+    // Changing the working directory affects the whole process, so all checks are
+    // done in a single test.
+    std::env::set_current_dir(dir.path())?;
+    let cwd = std::env::current_dir()?;
+
+    let actual = BugReport::from_name_and_version("dummy", "0.1")
+        .info(CurrentWorkingDirectory::default().with_entries())
+        .format::<Markdown>();
+
+    let expected = format!(
+        "#### Current working directory
+
+- Path: {}
+- Inside git worktree: no
+
+Entries:
+- file.txt, 14 bytes
+- repo{}
+
+",
+        cwd.to_string_lossy(),
+        std::path::MAIN_SEPARATOR
+    );
+
+    assert_eq!(expected, actual);
+
+    std::env::set_current_dir(cwd.join("repo").join("src"))?;
+
+    let actual = BugReport::from_name_and_version("dummy", "0.1")
+        .info(CurrentWorkingDirectory::default())
+        .format::<Markdown>();
+
+    let expected = format!(
+        "#### Current working directory
+
+- Path: {}
+- Inside git worktree: yes ({})
+
+",
+        cwd.join("repo").join("src").to_string_lossy(),
+        cwd.join("repo").to_string_lossy()
+    );
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
@@ -15,7 +15,7 @@ fn dir_not_found() {
 
     let expected = "#### No dir
 
-'this-dir-does-not-exist' not found
+> ⚠ 'this-dir-does-not-exist' not found
 
 ";
 
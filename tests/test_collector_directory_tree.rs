@@ -0,0 +1,145 @@
+#![cfg(all(unix, feature = "format_markdown"))]
+
+use std::fs;
+use std::os::unix::fs::{PermissionsExt, symlink};
+use std::path::Path;
+
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+use bugreport::{BugReport, collector::DirectoryTree, format::Markdown};
+
+fn report(collector: DirectoryTree) -> String {
+    BugReport::from_name_and_version("dummy", "0.1")
+        .info(collector)
+        .format::<Markdown>()
+}
+
+fn create_tree(root: &Path) -> Result<(), std::io::Error> {
+    fs::write(root.join("config.toml"), "key = 1\n")?;
+    fs::write(root.join("debug.log"), "log")?;
+    fs::create_dir_all(root.join("themes").join("extra"))?;
+    fs::write(root.join("themes").join("dark.toml"), "dark")?;
+    fs::write(root.join("themes").join("extra").join("neon.toml"), "neon")?;
+    fs::create_dir(root.join("target"))?;
+    fs::write(root.join("target").join("output"), "")?;
+    symlink("themes/dark.toml", root.join("current"))?;
+    symlink("does-not-exist", root.join("broken"))?;
+    Ok(())
+}
+
+#[test]
+fn full_tree() -> Result<(), std::io::Error> {
+    let dir = tempdir()?;
+    create_tree(dir.path())?;
+
+    let actual = report(DirectoryTree::new("Tree", dir.path()));
+
+    let expected = "#### Tree
+
+- broken -> does-not-exist (dangling)
+- config.toml, 8 bytes
+- current -> themes/dark.toml
+- debug.log, 3 bytes
+- target/
+  - output, 0 bytes
+- themes/
+  - dark.toml, 4 bytes
+  - extra/
+    - neon.toml, 4 bytes
+
+";
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+fn depth_filters_and_limits() -> Result<(), std::io::Error> {
+    let dir = tempdir()?;
+    create_tree(dir.path())?;
+
+    let actual = report(
+        DirectoryTree::new("Tree", dir.path())
+            .max_depth(2)
+            .include("*.toml")
+            .exclude("target")
+            .exclude("current"),
+    );
+
+    let expected = "#### Tree
+
+- config.toml, 8 bytes
+- themes/
+  - dark.toml, 4 bytes
+  - extra/
+
+";
+
+    assert_eq!(expected, actual);
+
+    let actual = report(DirectoryTree::new("Tree", dir.path()).max_entries(3));
+
+    let expected = "#### Tree
+
+- broken -> does-not-exist (dangling)
+- config.toml, 8 bytes
+- current -> themes/dark.toml
+- … (limit of 3 entries reached)
+
+";
+
+    assert_eq!(expected, actual);
+
+    // The limit is reached inside a subdirectory, but the note is only shown once
+    let actual = report(DirectoryTree::new("Tree", dir.path()).max_entries(5));
+
+    let expected = "#### Tree
+
+- broken -> does-not-exist (dangling)
+- config.toml, 8 bytes
+- current -> themes/dark.toml
+- debug.log, 3 bytes
+- target/
+  - … (limit of 5 entries reached)
+
+";
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+fn permissions() -> Result<(), std::io::Error> {
+    let dir = tempdir()?;
+    let file = dir.path().join("script.sh");
+    fs::write(&file, "")?;
+    fs::set_permissions(&file, fs::Permissions::from_mode(0o754))?;
+
+    let actual = report(DirectoryTree::new("Tree", dir.path()).permissions());
+
+    let expected = "#### Tree
+
+- script.sh, 0 bytes, rwxr-xr--
+
+";
+
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+fn dir_not_found() {
+    let actual = report(DirectoryTree::new("No dir", "this-dir-does-not-exist"));
+
+    let expected = "#### No dir
+
+> ⚠ 'this-dir-does-not-exist' not found
+
+";
+
+    assert_eq!(expected, actual);
+}
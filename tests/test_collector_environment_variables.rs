@@ -0,0 +1,112 @@
+#![cfg(feature = "format_markdown")]
+
+use pretty_assertions::assert_eq;
+
+use bugreport::{bugreport, collector::*, format::Markdown};
+
+fn set_vars(prefix: &str) {
+    unsafe {
+        std::env::set_var(format!("{}_B", prefix), "two words");
+        std::env::set_var(format!("{}_A", prefix), "1");
+        std::env::set_var(format!("{}_API_TOKEN", prefix), "abc123");
+    }
+}
+
+fn code(vars: &str) -> String {
+    format!("#### Environment variables\n\n```bash\n{}\n```\n\n", vars)
+}
+
+#[test]
+fn prefix() {
+    set_vars("BUGREPORT_PREFIX");
+
+    let actual = bugreport!()
+        .info(EnvironmentVariables::prefix("BUGREPORT_PREFIX_"))
+        .format::<Markdown>();
+
+    assert_eq!(
+        actual,
+        code(
+            "BUGREPORT_PREFIX_A=1\n\
+             BUGREPORT_PREFIX_API_TOKEN=<set, 6 chars>\n\
+             BUGREPORT_PREFIX_B='two words'"
+        )
+    );
+}
+
+#[test]
+fn glob() {
+    set_vars("BUGREPORT_GLOB");
+
+    let actual = bugreport!()
+        .info(EnvironmentVariables::glob("BUGREPORT_GLOB_?").show_secrets())
+        .format::<Markdown>();
+
+    assert_eq!(
+        actual,
+        code("BUGREPORT_GLOB_A=1\nBUGREPORT_GLOB_B='two words'")
+    );
+}
+
+#[test]
+#[cfg(feature = "regex")]
+fn regex() {
+    set_vars("BUGREPORT_REGEX");
+
+    let actual = bugreport!()
+        .info(EnvironmentVariables::regex(
+            bugreport::Regex::new("^BUGREPORT_REGEX_(A|API_TOKEN)$").unwrap(),
+        ))
+        .format::<Markdown>();
+
+    assert_eq!(
+        actual,
+        code("BUGREPORT_REGEX_A=1\nBUGREPORT_REGEX_API_TOKEN=<set, 6 chars>")
+    );
+}
+
+#[test]
+fn secrets_in_list() {
+    set_vars("BUGREPORT_LIST");
+
+    let actual = bugreport!()
+        .info(EnvironmentVariables::list(&[
+            "BUGREPORT_LIST_API_TOKEN",
+            "BUGREPORT_LIST_UNSET",
+        ]))
+        .format::<Markdown>();
+
+    assert_eq!(
+        actual,
+        code("BUGREPORT_LIST_API_TOKEN=<set, 6 chars>\nBUGREPORT_LIST_UNSET=<not set>")
+    );
+}
+
+#[test]
+fn only_whole_name_parts_are_secret() {
+    unsafe {
+        std::env::set_var("BUGREPORT_KEYMAP", "de");
+        std::env::set_var("BUGREPORT_MONKEY", "yes");
+    }
+
+    let actual = bugreport!()
+        .info(EnvironmentVariables::list(&[
+            "BUGREPORT_KEYMAP",
+            "BUGREPORT_MONKEY",
+        ]))
+        .format::<Markdown>();
+
+    assert_eq!(actual, code("BUGREPORT_KEYMAP=de\nBUGREPORT_MONKEY=yes"));
+}
+
+#[test]
+fn no_match() {
+    let actual = bugreport!()
+        .info(EnvironmentVariables::prefix("BUGREPORT_DOES_NOT_EXIST_"))
+        .format::<Markdown>();
+
+    assert_eq!(
+        actual,
+        "#### Environment variables\n\nNo matching environment variables are set.\n\n"
+    );
+}
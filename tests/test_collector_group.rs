@@ -0,0 +1,72 @@
+#![cfg(any(feature = "format_markdown", feature = "format_plaintext"))]
+
+use pretty_assertions::assert_eq;
+
+use bugreport::{
+    BugReport,
+    collector::{Group, SoftwareVersion},
+};
+
+fn nested_report() -> BugReport<'static> {
+    BugReport::from_name_and_version("dummy", "0.1").info(
+        Group::new("Outer")
+            .info(SoftwareVersion::default())
+            .info(Group::new("Inner").info(SoftwareVersion::custom("0.2"))),
+    )
+}
+
+#[test]
+#[cfg(feature = "format_markdown")]
+fn markdown() {
+    let actual = nested_report().format::<bugreport::format::Markdown>();
+
+    let expected = "#### Outer
+
+##### Software version
+
+dummy 0.1
+
+##### Inner
+
+###### Software version
+
+dummy 0.2
+
+";
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+#[cfg(feature = "format_plaintext")]
+fn plaintext() {
+    let actual = nested_report().format::<bugreport::format::Plaintext>();
+
+    let expected = "---------------------Outer----------------------
+  --------------Software version--------------
+dummy 0.1
+
+  -------------------Inner--------------------
+    ------------Software version------------
+dummy 0.2
+
+";
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+#[cfg(feature = "format_plaintext")]
+fn deep_nesting() {
+    let mut group = Group::new("Level 20").info(SoftwareVersion::default());
+    for level in (1..20).rev() {
+        group = Group::new(&format!("Level {}", level)).info(group);
+    }
+
+    let actual = BugReport::from_name_and_version("dummy", "0.1")
+        .info(group)
+        .format::<bugreport::format::Plaintext>();
+
+    assert!(actual.contains(&format!("{}Level 20\n", " ".repeat(2 * 19))));
+    assert!(actual.ends_with("dummy 0.1\n\n"));
+}
@@ -0,0 +1,40 @@
+#![cfg(all(unix, feature = "format_markdown"))]
+
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+use bugreport::{BugReport, collector::SoftwareVersion, format::Markdown};
+
+#[test]
+fn edit() -> Result<(), std::io::Error> {
+    // Pretend to be a user that appends a line to the report
+    unsafe {
+        std::env::set_var("VISUAL", r#"sh -c 'echo "Added by the user" >> "$0"'"#);
+    }
+
+    let mut report =
+        BugReport::from_name_and_version("dummy", "0.1").info(SoftwareVersion::default());
+
+    let expected = "#### Software version
+
+dummy 0.1
+
+Added by the user
+";
+
+    assert_eq!(expected, report.edit::<Markdown>()?);
+
+    let dir = tempdir()?;
+    let path = dir.path().join("report.md");
+    report.edit_to_file::<Markdown>(&path)?;
+
+    assert_eq!(expected, std::fs::read_to_string(path)?);
+
+    // Only the current user can read the temporary file
+    unsafe {
+        std::env::set_var("VISUAL", r#"sh -c 'ls -l "$0" | cut -c 1-10 > "$0"'"#);
+    }
+    assert_eq!("-rw-------\n", report.edit::<Markdown>()?);
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use std::result::Result;
+
+use pretty_assertions::assert_eq;
+
+use bugreport::{
+    BugReport, CrateInfo, FailedSections,
+    collector::*,
+    report::{ReportEntry, ReportSection},
+};
+
+struct Fixed {
+    title: &'static str,
+    result: fn() -> Result<ReportEntry, CollectionError>,
+}
+
+impl Collector for Fixed {
+    fn description(&self) -> &str {
+        self.title
+    }
+
+    fn collect(&mut self, _: &CrateInfo) -> Result<ReportEntry, CollectionError> {
+        (self.result)()
+    }
+}
+
+fn report(failed_sections: FailedSections) -> Vec<ReportSection> {
+    BugReport::from_name_and_version("dummy", "0.1")
+        .failed_sections(failed_sections)
+        .info(Fixed {
+            title: "Works",
+            result: || Ok(ReportEntry::Text("ok".into())),
+        })
+        .info(Fixed {
+            title: "Fails",
+            result: || Err(CollectionError::PermissionDenied("'/root'".into())),
+        })
+        .info(Fixed {
+            title: "Unsupported",
+            result: || Err(CollectionError::NotApplicable),
+        })
+        .collect()
+        .sections
+}
+
+fn works() -> ReportSection {
+    ReportSection {
+        title: "Works".into(),
+        entry: ReportEntry::Text("ok".into()),
+    }
+}
+
+#[test]
+fn show() {
+    assert_eq!(
+        report(FailedSections::Show),
+        vec![
+            works(),
+            ReportSection {
+                title: "Fails".into(),
+                entry: ReportEntry::Error("Permission denied: '/root'".into()),
+            },
+        ]
+    );
+}
+
+#[test]
+fn hide() {
+    assert_eq!(report(FailedSections::Hide), vec![works()]);
+}
+
+#[test]
+fn summarize() {
+    assert_eq!(
+        report(FailedSections::Summarize),
+        vec![
+            works(),
+            ReportSection {
+                title: "Collection errors".into(),
+                entry: ReportEntry::List(vec![ReportEntry::key_value(
+                    "Fails",
+                    "Permission denied: '/root'"
+                )]),
+            },
+        ]
+    );
+}
@@ -0,0 +1,257 @@
+#![cfg(any(feature = "format_markdown", feature = "format_plaintext"))]
+
+use std::result::Result;
+
+use pretty_assertions::assert_eq;
+
+use bugreport::{BugReport, CrateInfo, collector::*, format::Format, report::ReportEntry};
+
+struct Structured {}
+
+impl Collector for Structured {
+    fn description(&self) -> &str {
+        "Structured"
+    }
+
+    fn collect(&mut self, _: &CrateInfo) -> Result<ReportEntry, CollectionError> {
+        Ok(ReportEntry::Concat(vec![
+            ReportEntry::List(vec![
+                ReportEntry::key_value("OS", "Linux"),
+                ReportEntry::key_value("Architecture", "x86_64"),
+            ]),
+            ReportEntry::Table {
+                headers: vec!["Name".into(), "Size".into()],
+                rows: vec![
+                    vec!["config.toml".into(), "120".into()],
+                    vec!["a|b".into(), "7".into()],
+                ],
+            },
+        ]))
+    }
+}
+
+fn report() -> BugReport<'static> {
+    BugReport::from_name_and_version("dummy", "0.1").info(Structured {})
+}
+
+#[test]
+#[cfg(feature = "format_markdown")]
+fn markdown() {
+    let actual = report().format::<bugreport::format::Markdown>();
+
+    let expected = r"#### Structured
+
+- OS: Linux
+- Architecture: x86_64
+
+| Name | Size |
+| --- | --- |
+| config.toml | 120 |
+| a\|b | 7 |
+
+";
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+#[cfg(feature = "format_plaintext")]
+fn plaintext() {
+    let actual = report().format::<bugreport::format::Plaintext>();
+
+    let expected = "-------------------Structured-------------------
+- OS:           Linux
+- Architecture: x86_64
+Name         Size
+-----------  ----
+config.toml  120
+a|b          7
+
+";
+
+    assert_eq!(expected, actual);
+}
+
+struct Fixed {
+    title: &'static str,
+    entry: ReportEntry,
+}
+
+impl Collector for Fixed {
+    fn description(&self) -> &str {
+        self.title
+    }
+
+    fn collect(&mut self, _: &CrateInfo) -> Result<ReportEntry, CollectionError> {
+        Ok(self.entry.clone())
+    }
+}
+
+#[cfg(feature = "format_markdown")]
+fn code(code: &str) -> ReportEntry {
+    ReportEntry::Code(bugreport::report::Code {
+        language: None,
+        code: code.into(),
+    })
+}
+
+#[test]
+#[cfg(feature = "format_markdown")]
+fn markdown_code_fence() {
+    let actual = BugReport::from_name_and_version("dummy", "0.1")
+        .info(Fixed {
+            title: "README.md",
+            entry: code("Example:\n```rust\nlet x = 1;\n```"),
+        })
+        .format::<bugreport::format::Markdown>();
+
+    let expected = "#### README.md
+
+````
+Example:
+```rust
+let x = 1;
+```
+````
+
+";
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+#[cfg(feature = "format_markdown")]
+fn github_markdown() {
+    let log: Vec<String> = (1..=30).map(|i| format!("line {}", i)).collect();
+
+    let actual = BugReport::from_name_and_version("dummy", "0.1")
+        .info(Fixed {
+            title: "Short <T>",
+            entry: ReportEntry::Text("*not* <b>bold</b>\n# no heading".into()),
+        })
+        .info(Fixed {
+            title: "Vec<T> log",
+            entry: code(&log.join("\n")),
+        })
+        .format::<bugreport::format::GithubMarkdown>();
+
+    let expected = format!(
+        "#### Short \\<T\\>
+
+\\*not\\* \\<b\\>bold\\</b\\>
+\\# no heading
+
+<details>
+<summary>Vec&lt;T&gt; log</summary>
+
+```
+{}
+```
+
+</details>
+
+",
+        log.join("\n")
+    );
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+#[cfg(feature = "format_markdown")]
+fn github_markdown_table_and_placeholder() {
+    let actual = BugReport::from_name_and_version("dummy", "0.1")
+        .info(Fixed {
+            title: "Table",
+            entry: ReportEntry::Concat(vec![
+                ReportEntry::Table {
+                    headers: vec!["<details>".into()],
+                    rows: vec![vec!["*x*|y".into()]],
+                },
+                ReportEntry::Placeholder("a --> b".into()),
+            ]),
+        })
+        .format::<bugreport::format::GithubMarkdown>();
+
+    let expected = "#### Table
+
+
+| \\<details\\> |
+| --- |
+| \\*x\\*\\|y |
+<!-- a - -> b -->
+
+";
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+#[cfg(feature = "format_markdown")]
+fn markdown_options() {
+    let mut format = bugreport::format::Markdown::default().fold_sections(1);
+
+    let actual = BugReport::from_name_and_version("dummy", "0.1")
+        .info(Fixed {
+            title: "One line",
+            entry: ReportEntry::Text("_text_".into()),
+        })
+        .info(Fixed {
+            title: "Code <block>",
+            entry: code("a"),
+        })
+        .format_with(&mut format);
+
+    let expected = "#### One line
+
+_text_
+
+<details>
+<summary>Code &lt;block&gt;</summary>
+
+```
+a
+```
+
+</details>
+
+";
+
+    assert_eq!(expected, actual);
+}
+
+/// Custom formats can read the crate information in their header.
+#[derive(Default)]
+struct Header {}
+
+impl Format for Header {
+    fn format_header(&mut self, crate_info: &CrateInfo) -> String {
+        format!(
+            "{} {} ({})\n",
+            crate_info.name(),
+            crate_info.version(),
+            crate_info.git_hash().unwrap_or("unknown")
+        )
+    }
+
+    fn format_section(&mut self, _: &str) -> String {
+        String::new()
+    }
+
+    fn format_entry(&mut self, _: &ReportEntry) -> String {
+        String::new()
+    }
+
+    fn format_section_end(&mut self) -> String {
+        String::new()
+    }
+}
+
+#[test]
+fn custom_format_header() {
+    assert_eq!(report().format::<Header>(), "dummy 0.1 (unknown)\n");
+
+    let mut report = report();
+    report.set_git_hash(Some("abc123"));
+    assert_eq!(report.format::<Header>(), "dummy 0.1 (abc123)\n");
+}
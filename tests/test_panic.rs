@@ -0,0 +1,51 @@
+#![cfg(all(unix, feature = "format_markdown"))]
+
+use tempfile::tempdir;
+
+use bugreport::{BugReport, collector::SoftwareVersion, format::Markdown};
+
+#[test]
+fn report_is_written_on_panic() -> Result<(), std::io::Error> {
+    let dir = tempdir()?;
+
+    // The report is written to the temporary directory
+    unsafe {
+        std::env::set_var("TMPDIR", dir.path());
+    }
+
+    bugreport::panic::install::<Markdown>(|| {
+        BugReport::from_name_and_version("dummy", "0.1").info(SoftwareVersion::default())
+    });
+
+    let result = std::thread::Builder::new()
+        .name("worker".into())
+        .spawn(|| panic!("something went wrong"))?
+        .join();
+    assert!(result.is_err());
+
+    let _ = std::panic::take_hook();
+
+    let files: Vec<_> = std::fs::read_dir(dir.path())?.collect::<Result<_, _>>()?;
+    assert_eq!(files.len(), 1);
+
+    let name = files[0].file_name().to_string_lossy().into_owned();
+    assert!(name.starts_with("dummy-panic-"));
+    assert!(name.ends_with(".log"));
+
+    // Only the current user can read the report
+    use std::os::unix::fs::PermissionsExt;
+    let mode = files[0].metadata()?.permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+
+    let content = std::fs::read_to_string(files[0].path())?;
+    assert!(content.starts_with(
+        "#### Panic
+
+- Message: something went wrong
+- Location: tests/test_panic.rs:"
+    ));
+    assert!(content.contains("- Thread: worker\n```\n"));
+    assert!(content.ends_with("#### Software version\n\ndummy 0.1\n\n"));
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::result::Result;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+use pretty_assertions::assert_eq;
+
+use bugreport::{BugReport, CrateInfo, collector::*, report::ReportEntry};
+
+struct Slow {
+    title: String,
+    delay: Duration,
+}
+
+impl Collector for Slow {
+    fn description(&self) -> &str {
+        &self.title
+    }
+
+    fn collect(&mut self, _: &CrateInfo) -> Result<ReportEntry, CollectionError> {
+        sleep(self.delay);
+        Ok(ReportEntry::Text(format!("slept {:?}", self.delay)))
+    }
+}
+
+#[test]
+fn order_is_preserved() {
+    let mut report = BugReport::from_name_and_version("dummy", "0.1").parallel(4);
+
+    // The first collectors take longest, so they finish last
+    for i in 0..4 {
+        report = report.parallel_info(Slow {
+            title: format!("Collector {}", i),
+            delay: Duration::from_millis(400 - 100 * i),
+        });
+    }
+
+    let start = Instant::now();
+    let report = report.collect();
+    assert!(start.elapsed() < Duration::from_millis(1000));
+
+    let sections: Vec<_> = report
+        .sections
+        .into_iter()
+        .map(|section| (section.title, section.entry))
+        .collect();
+
+    assert_eq!(
+        sections,
+        (0..4)
+            .map(|i| (
+                format!("Collector {}", i),
+                ReportEntry::Text(format!("slept {}ms", 400 - 100 * i))
+            ))
+            .collect::<Vec<_>>()
+    );
+}
+
+/// Collectors that are not `Send` can still be added to a parallel report.
+struct Local {
+    calls: Rc<Cell<usize>>,
+}
+
+impl Collector for Local {
+    fn description(&self) -> &str {
+        "Local"
+    }
+
+    fn collect(&mut self, _: &CrateInfo) -> Result<ReportEntry, CollectionError> {
+        self.calls.set(self.calls.get() + 1);
+        Ok(ReportEntry::Text("local".into()))
+    }
+}
+
+#[test]
+fn local_collectors() {
+    let calls = Rc::new(Cell::new(0));
+
+    let report = BugReport::from_name_and_version("dummy", "0.1")
+        .parallel(2)
+        .parallel_info(Slow {
+            title: "Slow".into(),
+            delay: Duration::from_millis(10),
+        })
+        .info(Local {
+            calls: calls.clone(),
+        })
+        .collect();
+
+    assert_eq!(calls.get(), 1);
+    assert_eq!(
+        report
+            .sections
+            .iter()
+            .map(|section| section.title.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Slow", "Local"]
+    );
+}
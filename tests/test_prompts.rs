@@ -0,0 +1,56 @@
+#![cfg(feature = "format_markdown")]
+
+use pretty_assertions::assert_eq;
+
+use bugreport::{BugReport, collector::*, format::Markdown};
+
+#[test]
+fn prompts_come_first() {
+    let actual = BugReport::from_name_and_version("dummy", "0.1")
+        .info(SoftwareVersion::default())
+        .prompt("What did you do?", "Steps to reproduce")
+        .format::<Markdown>();
+
+    let expected = "#### What did you do?
+
+<!-- Steps to reproduce -->
+
+#### Software version
+
+dummy 0.1
+
+";
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn issue_template() {
+    let report = BugReport::from_name_and_version("dummy", "0.1")
+        .issue_template()
+        .collect();
+
+    let titles: Vec<_> = report.sections.iter().map(|s| s.title.as_str()).collect();
+    assert_eq!(
+        titles,
+        [
+            "What did you do?",
+            "What did you expect to happen?",
+            "What happened instead?"
+        ]
+    );
+}
+
+#[test]
+fn no_consent_needed_for_prompts() {
+    let mut output = vec![];
+    let report = BugReport::from_name_and_version("dummy", "0.1")
+        .issue_template()
+        .info(SoftwareVersion::default())
+        .collect_with_consent(&b"y\n"[..], &mut output);
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("Software version"));
+    assert!(!output.contains("What did you do?"));
+    assert_eq!(report.sections.len(), 4);
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "serde")]
+
+use pretty_assertions::assert_eq;
+
+use bugreport::{
+    bugreport,
+    collector::{CommandLine, SoftwareVersion},
+    report::{Code, Report, ReportEntry, ReportSection},
+};
+
+#[test]
+fn roundtrip() {
+    let report = bugreport!()
+        .info(SoftwareVersion::default())
+        .info(CommandLine::default())
+        .collect();
+
+    let json = serde_json::to_string(&report).unwrap();
+    let deserialized: Report = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(report, deserialized);
+}
+
+fn all_entries() -> Report {
+    Report {
+        crate_name: "dummy".into(),
+        crate_version: "0.1".into(),
+        git_hash: Some("abc123".into()),
+        sections: vec![ReportSection {
+            title: "All entries".into(),
+            entry: ReportEntry::Concat(vec![
+                ReportEntry::Text("text".into()),
+                ReportEntry::Code(Code {
+                    language: Some("bash".into()),
+                    code: "echo".into(),
+                }),
+                ReportEntry::List(vec![ReportEntry::key_value("key", "value")]),
+                ReportEntry::Table {
+                    headers: vec!["a".into()],
+                    rows: vec![vec!["1".into()]],
+                },
+                ReportEntry::Error("error".into()),
+                ReportEntry::Placeholder("hint".into()),
+                ReportEntry::Section {
+                    title: "nested".into(),
+                    body: Box::new(ReportEntry::Text("body".into())),
+                },
+            ]),
+        }],
+    }
+}
+
+#[test]
+fn schema() {
+    let report = Report {
+        sections: vec![ReportSection {
+            title: "Software version".into(),
+            entry: ReportEntry::Text("dummy 0.1".into()),
+        }],
+        git_hash: None,
+        ..all_entries()
+    };
+
+    assert_eq!(
+        serde_json::to_string(&report).unwrap(),
+        r#"{"crate":{"name":"dummy","version":"0.1","git_hash":null},"sections":[{"title":"Software version","entry":{"type":"text","text":"dummy 0.1"}}]}"#
+    );
+}
+
+#[test]
+#[cfg(feature = "format_json")]
+fn same_schema_as_json_format() {
+    use bugreport::{BugReport, format::Json};
+
+    let report = all_entries();
+
+    let deserialized: Report = serde_json::from_str(&report.format::<Json>()).unwrap();
+    assert_eq!(report, deserialized);
+
+    let collected = BugReport::from_name_and_version("dummy", "0.1")
+        .info(SoftwareVersion::default())
+        .format::<Json>();
+    let deserialized: Report = serde_json::from_str(&collected).unwrap();
+    assert_eq!(deserialized.crate_name, "dummy");
+}
@@ -0,0 +1,87 @@
+use std::result::Result;
+
+use pretty_assertions::assert_eq;
+
+use bugreport::{
+    BugReport, CrateInfo,
+    collector::*,
+    format::Format,
+    report::{Code, ReportEntry},
+};
+
+struct Nested {}
+
+impl Collector for Nested {
+    fn description(&self) -> &str {
+        "Nested"
+    }
+
+    fn collect(&mut self, _: &CrateInfo) -> Result<ReportEntry, CollectionError> {
+        Ok(ReportEntry::List(vec![
+            ReportEntry::key_value("Answer", "42"),
+            ReportEntry::Concat(vec![
+                ReportEntry::Text("log:".into()),
+                ReportEntry::Code(Code {
+                    language: None,
+                    code: "first line\nsecond line".into(),
+                }),
+            ]),
+        ]))
+    }
+}
+
+/// A format that only implements the `String`-based methods.
+#[derive(Default)]
+struct Titles {}
+
+impl Format for Titles {
+    fn format_section(&mut self, title: &str) -> String {
+        format!("[{}]", title)
+    }
+
+    fn format_entry(&mut self, _: &ReportEntry) -> String {
+        "…".into()
+    }
+}
+
+fn report() -> BugReport<'static> {
+    BugReport::from_name_and_version("dummy", "0.1")
+        .info(Nested {})
+        .info(Nested {})
+}
+
+fn written<F: Format>() -> String {
+    let mut output = vec![];
+    report().write_to::<F, _>(&mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn string_based_format() {
+    assert_eq!(written::<Titles>(), "[Nested]…\n[Nested]…\n");
+}
+
+#[test]
+#[cfg(feature = "format_markdown")]
+fn markdown() {
+    assert_eq!(
+        written::<bugreport::format::Markdown>(),
+        "#### Nested\n\
+         \n\
+         - Answer: 42\n\
+         - log:\n  \
+         ```\n  \
+         first line\n  \
+         second line\n  \
+         ```\n\
+         \n\
+         #### Nested\n\
+         \n\
+         - Answer: 42\n\
+         - log:\n  \
+         ```\n  \
+         first line\n  \
+         second line\n  \
+         ```\n\n"
+    );
+}